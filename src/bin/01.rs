@@ -10,7 +10,7 @@ static DIGITS: [&str; 18] = [
     "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-fn get_digits_spelled(input: &str) -> (u32, u32) {
+fn get_digits_spelled(input: &str) -> Option<(u32, u32)> {
     let &first = DIGITS
         .iter()
         .min_by_key(|&&digit| input.find(digit).unwrap_or(usize::MAX))
@@ -19,56 +19,54 @@ fn get_digits_spelled(input: &str) -> (u32, u32) {
     let &last = DIGITS
         .iter()
         .filter(|&&digit| input.contains(digit))
-        .max_by_key(|&&digit| input.rfind(digit).unwrap_or(usize::MIN))
-        .unwrap();
+        .max_by_key(|&&digit| input.rfind(digit).unwrap_or(usize::MIN))?;
 
     let first_digit = DIGITS.iter().enumerate().find(|(_, &digit)| digit == first).unwrap().0 as u32;
     let last_digit = DIGITS.iter().enumerate().find(|(_, &digit)| digit == last).unwrap().0 as u32;
 
-    (first_digit % 9 + 1, last_digit % 9 + 1)
+    Some((first_digit % 9 + 1, last_digit % 9 + 1))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .par_bridge()
-            .map(get_digits)
-            .map(|digits| digits.first().unwrap() * 10 + digits.last().unwrap())
-            .sum(),
-    )
+    input
+        .lines()
+        .par_bridge()
+        .map(get_digits)
+        .map(|digits| Some(digits.first()? * 10 + digits.last()?))
+        .sum()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .par_bridge()
-            .map(get_digits_spelled)
-            .map(|(first, last)| first * 10 + last)
-            .sum(),
-    )
+    input
+        .lines()
+        .par_bridge()
+        .map(get_digits_spelled)
+        .map(|digits| digits.map(|(first, last)| first * 10 + last))
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
     fn test_get_digits_spelled() {
         // example ones
-        assert_eq!(get_digits_spelled("two1nine"), (2, 9));
-        assert_eq!(get_digits_spelled("eightwothree"), (8, 3));
-        assert_eq!(get_digits_spelled("abcone2threexyz"), (1, 3));
-        assert_eq!(get_digits_spelled("xtwone3four"), (2, 4));
-        assert_eq!(get_digits_spelled("4nineeightseven2"), (4, 2));
-        assert_eq!(get_digits_spelled("zoneight234"), (1, 4));
-        assert_eq!(get_digits_spelled("7pqrstsixteen"), (7, 6));
+        assert_eq!(get_digits_spelled("two1nine"), Some((2, 9)));
+        assert_eq!(get_digits_spelled("eightwothree"), Some((8, 3)));
+        assert_eq!(get_digits_spelled("abcone2threexyz"), Some((1, 3)));
+        assert_eq!(get_digits_spelled("xtwone3four"), Some((2, 4)));
+        assert_eq!(get_digits_spelled("4nineeightseven2"), Some((4, 2)));
+        assert_eq!(get_digits_spelled("zoneight234"), Some((1, 4)));
+        assert_eq!(get_digits_spelled("7pqrstsixteen"), Some((7, 6)));
 
         // custom ones
-        assert_eq!(get_digits_spelled("oneight"), (1, 8));
-        assert_eq!(get_digits_spelled("2eight4264"), (2, 4));
-        assert_eq!(get_digits_spelled("three"), (3, 3));
+        assert_eq!(get_digits_spelled("oneight"), Some((1, 8)));
+        assert_eq!(get_digits_spelled("2eight4264"), Some((2, 4)));
+        assert_eq!(get_digits_spelled("three"), Some((3, 3)));
+        assert_eq!(get_digits_spelled("abc"), None);
     }
 
     #[test]
@@ -102,4 +100,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
         assert_eq!(result, Some(54076));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            let (one, two) = (part_one(input), part_two(input));
+            one.is_some() || two.is_some()
+        });
+    }
 }
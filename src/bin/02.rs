@@ -107,6 +107,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use advent_of_code::utils::Parsable;
 
     use super::*;
@@ -169,4 +171,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
         assert_eq!(result, Some(71535));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(DiceGame::parse)(input).is_ok()
+        });
+    }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::utils::grid::parse_grid;
 use advent_of_code::utils::location::{Access2d, Location};
 use std::collections::HashSet;
 
@@ -14,7 +15,8 @@ struct NumbersInCharMatrix {
     locations: Vec<Vec<Option<usize>>>,
 }
 
-fn find_numbers(data: &Vec<Vec<char>>) -> NumbersInCharMatrix {
+/// Numbers in the grid and the number at every location, `None` if a number doesn't fit into 32 bits.
+fn find_numbers(data: &Vec<Vec<char>>) -> Option<NumbersInCharMatrix> {
     let mut numbers = vec![];
     let mut number = NumberPointer {
         value: 0,
@@ -35,20 +37,20 @@ fn find_numbers(data: &Vec<Vec<char>>) -> NumbersInCharMatrix {
         }
 
         if char.is_ascii_digit() {
-            number.value = number.value * 10 + char.to_digit(10).unwrap();
+            number.value = number.value.checked_mul(10)?.checked_add(char.to_digit(10).unwrap())?;
             locations.set_2d(location, Some(numbers.len()));
         }
     }
 
     numbers.push(number);
 
-    NumbersInCharMatrix { numbers, locations }
+    Some(NumbersInCharMatrix { numbers, locations })
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
 
-    let mut numbers = find_numbers(&data);
+    let mut numbers = find_numbers(&data)?;
 
     for location in Location::new(0, 0).iter_range(Location::new(data[0].len() as i32, data.len() as i32)) {
         if let Some(number) = numbers.locations.get_2d(location).unwrap() {
@@ -64,37 +66,41 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(numbers.numbers.into_iter().filter(|a| a.has_neighbour).map(|a| a.value).sum())
+    numbers
+        .numbers
+        .into_iter()
+        .filter(|a| a.has_neighbour)
+        .try_fold(0, |sum: u32, a| sum.checked_add(a.value))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    let numbers = find_numbers(&data);
-
-    Some(
-        Location::new(0, 0)
-            .iter_range(Location::new(data[0].len() as i32, data.len() as i32))
-            .filter(|loc| data.get_2d(*loc).unwrap() == &'*')
-            .map(|loc| {
-                loc.neighbours()
-                    .iter()
-                    .filter_map(|loc| numbers.locations.get_2d(*loc))
-                    .filter_map(|number| *number)
-                    .collect()
-            })
-            .filter(|set: &HashSet<_>| set.len() == 2)
-            .map(|set| {
-                set.into_iter()
-                    .map(|index| numbers.numbers.get(index).unwrap().value)
-                    .product::<u32>()
-            })
-            .sum(),
-    )
+    let data = parse_grid(input)?;
+
+    let numbers = find_numbers(&data)?;
+
+    Location::new(0, 0)
+        .iter_range(Location::new(data[0].len() as i32, data.len() as i32))
+        .filter(|loc| data.get_2d(*loc).unwrap() == &'*')
+        .map(|loc| {
+            loc.neighbours()
+                .iter()
+                .filter_map(|loc| numbers.locations.get_2d(*loc))
+                .filter_map(|number| *number)
+                .collect()
+        })
+        .filter(|set: &HashSet<_>| set.len() == 2)
+        .map(|set| {
+            set.into_iter()
+                .map(|index| numbers.numbers.get(index).unwrap().value)
+                .try_fold(1, u32::checked_mul)
+        })
+        .try_fold(0, |sum: u32, ratio| sum.checked_add(ratio?))
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -109,4 +115,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            let (one, two) = (part_one(input), part_two(input));
+            one.is_some() || two.is_some()
+        });
+    }
 }
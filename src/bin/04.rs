@@ -88,6 +88,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -101,4 +103,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(30));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(LotteryCard::parse)(input).is_ok()
+        });
+    }
 }
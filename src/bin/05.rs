@@ -208,6 +208,8 @@ pub fn part_two(input: &str) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -229,4 +231,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input(Day5::parse)(input).is_ok()
+        });
+    }
 }
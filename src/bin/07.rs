@@ -35,8 +35,12 @@ impl FromStr for CardValue {
             "Q" => Ok(Self::Value(12)),
             "K" => Ok(Self::Value(13)),
             "A" => Ok(Self::Value(14)),
-            _ if s.len() == 1 => Ok(Self::Value(s.chars().next().unwrap().to_digit(10).unwrap())),
-            _ => Err(()),
+            _ => s
+                .parse()
+                .ok()
+                .filter(|value| (2..10).contains(value))
+                .map(Self::Value)
+                .ok_or(()),
         }
     }
 }
@@ -172,6 +176,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -185,4 +191,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5905));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(BetHand::parse)(input).is_ok()
+        });
+    }
 }
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::iter::{zip, Cycle, Enumerate};
+use std::iter::{Cycle, Enumerate};
 use std::slice::Iter;
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take};
 use nom::character::complete::{char, line_ending};
@@ -10,8 +11,8 @@ use nom::combinator::value;
 use nom::multi::{count, many1, separated_list1};
 use nom::sequence::{delimited, separated_pair, terminated};
 use nom::IResult;
-use num::integer::lcm;
 
+use advent_of_code::utils::math::first_common_time;
 use advent_of_code::utils::{parse_input, Parsable};
 
 advent_of_code::solution!(8);
@@ -100,47 +101,70 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(day.walk_trough_desert("AAA").take_while(|(_, node)| node != &"ZZZ").count() as u32)
 }
 
+/// Times at which a ghost stands on an end node: a few before it enters its cycle, then periodically.
+struct Ghost {
+    once: Vec<i64>,
+    periodic: Vec<(i64, i64)>,
+}
+
+impl Ghost {
+    fn new(day: &Day8, start: &str) -> Option<Self> {
+        let (cycle_start, cycle_end) = find_cycle(&mut day.walk_trough_desert(start))?;
+        let period = (cycle_end - cycle_start) as i64;
+
+        let (once, periodic): (Vec<_>, Vec<_>) = day
+            .walk_trough_desert(start)
+            .take(cycle_end)
+            .enumerate()
+            .filter(|(_, (_, node))| node.ends_with('Z'))
+            .map(|(time, _)| time)
+            .partition(|&time| time < cycle_start);
+
+        Some(Self {
+            once: once.into_iter().map(|time| time as i64).collect(),
+            periodic: periodic.into_iter().map(|time| (time as i64, period)).collect(),
+        })
+    }
+
+    fn is_finished(&self, time: i64) -> bool {
+        self.once.contains(&time)
+            || self
+                .periodic
+                .iter()
+                .any(|&(offset, period)| time >= offset && (time - offset) % period == 0)
+    }
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let (_, day) = parse_input(Day8::parse)(input).unwrap();
 
-    let starts = day.graph.keys().filter(|key| key.ends_with('A')).collect::<Vec<_>>();
+    let ghosts = day
+        .graph
+        .keys()
+        .filter(|key| key.ends_with('A'))
+        .map(|start| Ghost::new(&day, start))
+        .collect::<Option<Vec<_>>>()?;
 
-    let cycles = starts
+    let before_cycles = ghosts
         .iter()
-        .map(|start| find_cycle(&mut day.walk_trough_desert(start)))
-        .collect::<Vec<_>>();
-
-    let winning_positions = zip(starts.iter(), cycles.iter())
-        .filter_map(|(start, cycle)| cycle.map(|(start_pos, end_pos)| (start, (start_pos, end_pos))))
-        .map(|(start, (_, length))| {
-            day.walk_trough_desert(start)
-                .enumerate()
-                .take(length)
-                .filter(|(_, (_, node))| node.ends_with('Z'))
-                .map(|(pos, _)| pos)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+        .flat_map(|ghost| ghost.once.iter().copied())
+        .filter(|&time| ghosts.iter().all(|ghost| ghost.is_finished(time)))
+        .min();
 
-    let cycle_lengths = cycles
+    let in_cycles = ghosts
         .iter()
-        .map(|cycle| cycle.map(|(start, end)| end - start))
-        .collect::<Vec<_>>();
-
-    // only solve the problem for one winning position on cycle, and ensure it's always at start
-    let is_solvable = zip(cycle_lengths.iter(), winning_positions.iter()).all(|(cycle_length, winning_position)| {
-        cycle_length.is_some() && winning_position.len() == 1 && winning_position[0] == cycle_length.unwrap()
-    });
+        .map(|ghost| ghost.periodic.iter().copied())
+        .multi_cartesian_product()
+        .filter_map(|cycles| first_common_time(&cycles))
+        .min();
 
-    if !is_solvable {
-        return None;
-    }
-
-    cycle_lengths.iter().map(|length| length.unwrap() as u64).reduce(lcm)
+    [before_cycles, in_cycles].into_iter().flatten().min().map(|time| time as u64)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -152,6 +176,33 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_part_two_with_offsets() {
+        let input = r#"
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+        "#
+        .trim();
+
+        let result = part_two(input);
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input(Day8::parse)(input).is_ok()
+        });
     }
 }
@@ -1,41 +1,29 @@
+use advent_of_code::utils::poly::NewtonPolynomial;
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 
 advent_of_code::solution!(9);
 
-fn derive(input: &[i32]) -> Vec<i32> {
-    input.iter().zip(input.iter().skip(1)).map(|(a, b)| b - a).collect::<Vec<_>>()
-}
-
-fn predict(input: &[i32]) -> i32 {
-    if input.iter().all(|&d| d == 0) {
-        return 0;
-    }
-
-    input.last().unwrap() + predict(&derive(input))
-}
-
-fn predict_past(input: &[i32]) -> i32 {
-    if input.iter().all(|&d| d == 0) {
-        return 0;
-    }
-
-    input.first().unwrap() - predict_past(&derive(input))
-}
-
 pub fn part_one(input: &str) -> Option<i32> {
     let (_, inputs) = parse_input_by_lines(Vec::<i32>::parse)(input).unwrap();
 
-    Some(inputs.iter().map(|input| predict(input)).sum())
+    Some(
+        inputs
+            .iter()
+            .map(|input| NewtonPolynomial::fit(input).eval(input.len() as i32))
+            .sum(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<i32> {
     let (_, inputs) = parse_input_by_lines(Vec::<i32>::parse)(input).unwrap();
 
-    Some(inputs.iter().map(|input| predict_past(input)).sum())
+    Some(inputs.iter().map(|input| NewtonPolynomial::fit(input).eval(-1)).sum())
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -49,4 +37,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(Vec::<i32>::parse)(input).is_ok()
+        });
+    }
 }
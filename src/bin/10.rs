@@ -4,8 +4,11 @@ use std::iter::{repeat_with, RepeatWith};
 use itertools::Itertools;
 use num::Zero;
 
+use advent_of_code::utils::grid::{flood_fill, parse_grid, Neighbourhood};
 use advent_of_code::utils::location::direction::*;
 use advent_of_code::utils::location::{Access2d, Location};
+use advent_of_code::utils::viz;
+use advent_of_code::utils::viz::terminal::{Colour, GridRenderer};
 
 advent_of_code::solution!(10);
 
@@ -47,12 +50,11 @@ fn move_trough<'a>(
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
 
     let start = Location::new(0, 0)
         .iter_range(Location::new(data[0].len() as i32, data.len() as i32))
-        .find(|loc| data.get_2d(*loc).unwrap() == &'S')
-        .unwrap();
+        .find(|loc| data.get_2d(*loc).unwrap() == &'S')?;
 
     let cardinal_directions: Vec<Location<i32>> = vec![LEFT, RIGHT, UP, DOWN];
 
@@ -89,17 +91,15 @@ pub fn get_edge_fill_map() -> HashMap<char, Vec<Location<i32>>> {
 enum Insides {
     Inside,
     Edge,
-    Outside,
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
 
     let map_end = Location::new(data[0].len() as i32, data.len() as i32);
     let start = Location::new(0, 0)
         .iter_range(map_end)
-        .find(|loc| data.get_2d(*loc).unwrap() == &'S')
-        .unwrap();
+        .find(|loc| data.get_2d(*loc).unwrap() == &'S')?;
 
     let cardinal_directions: Vec<Location<i32>> = vec![LEFT, RIGHT, UP, DOWN];
 
@@ -119,19 +119,21 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     let edge_fill_map = get_edge_fill_map();
     let mut fill = vec![vec![Insides::Inside; data[0].len() * 3]; data.len() * 3];
-    let direction = directions[0];
+    let direction = *directions.first()?;
 
-    move_trough(start, direction, &data)
+    let pipe_loop = move_trough(start, direction, &data)
         .while_some()
         .take_while(|loc| *loc != start)
-        .for_each(|loc| {
-            let base_loc = (loc * 3) + Location::new(1, 1);
-            let tile = data.get_2d(loc).unwrap();
+        .collect_vec();
 
-            for &fill_loc in edge_fill_map.get(tile).unwrap() {
-                fill.set_2d(base_loc + fill_loc, Insides::Edge);
-            }
-        });
+    pipe_loop.iter().for_each(|&loc| {
+        let base_loc = (loc * 3) + Location::new(1, 1);
+        let tile = data.get_2d(loc).unwrap();
+
+        for &fill_loc in edge_fill_map.get(tile).unwrap() {
+            fill.set_2d(base_loc + fill_loc, Insides::Edge);
+        }
+    });
 
     let start_base_loc = (start * 3) + Location::new(1, 1);
     directions.iter().for_each(|dir| {
@@ -139,25 +141,30 @@ pub fn part_two(input: &str) -> Option<usize> {
     });
     fill.set_2d(start_base_loc, Insides::Edge);
 
-    let mut next = vec![Location::new(0, 0)];
-    while let Some(current) = next.pop() {
-        if let Some(Insides::Inside) = fill.get_2d(current) {
-            fill.set_2d(current, Insides::Outside);
-            next.extend(current.neighbours().iter());
-        }
+    let outside = flood_fill(&fill, [Location::new(0, 0)], Neighbourhood::Moore, |&x| x == Insides::Inside);
+
+    let inside = Location::new(0, 0)
+        .iter_range(map_end)
+        .filter(|&loc| {
+            let loc = (loc * 3) + Location::new(1, 1);
+            fill.get_2d(loc) == Some(&Insides::Inside) && outside.get_2d(loc) == Some(&false)
+        })
+        .collect_vec();
+
+    if viz::is_enabled() {
+        let renderer = GridRenderer::new(&data, |&c| c.into())
+            .highlight(pipe_loop.iter().copied().chain([start]), Colour::Blue)
+            .mark(inside.iter().copied(), 'I', Colour::Green);
+        eprint!("{renderer}");
     }
 
-    Some(
-        Location::new(0, 0)
-            .iter_range(map_end)
-            .filter_map(|loc| fill.get_2d((loc * 3) + Location::new(1, 1)))
-            .filter(|&&x| x == Insides::Inside)
-            .count(),
-    )
+    Some(inside.len())
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -171,4 +178,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            let (one, two) = (part_one(input), part_two(input));
+            one.is_some() || two.is_some()
+        });
+    }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::utils::grid::parse_grid;
 use advent_of_code::utils::location::{Access2d, Location};
 use itertools::Itertools;
 use num::abs;
@@ -50,19 +51,21 @@ fn sum_all_paths(galaxy_coordinates: Vec<Location<i64>>) -> i64 {
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
     let galaxy_coordinates = calculate_galaxy_coordinates(&data, 1);
     Some(sum_all_paths(galaxy_coordinates))
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
     let galaxy_coordinates = calculate_galaxy_coordinates(&data, 999999);
     Some(sum_all_paths(galaxy_coordinates))
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -76,4 +79,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(82000210));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            let (one, two) = (part_one(input), part_two(input));
+            one.is_some() || two.is_some()
+        });
+    }
 }
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
@@ -9,6 +8,7 @@ use nom::multi::{many1, separated_list1};
 use nom::IResult;
 use rayon::prelude::*;
 
+use advent_of_code::utils::memo::Memo;
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 
 advent_of_code::solution!(12);
@@ -50,7 +50,11 @@ impl Parsable<'_> for Day12 {
     }
 }
 
-fn try_a_lot(springs: &[Spring], sizes: &[usize], ident: usize) -> u64 {
+/// Counts arrangements of the remaining springs, recursing on suffixes of both slices.
+fn count_arrangements<'a>(
+    recurse: &mut dyn FnMut((&'a [Spring], &'a [usize])) -> u64,
+    (springs, sizes): (&'a [Spring], &'a [usize]),
+) -> u64 {
     if springs.len() + 1 < sizes.iter().sum::<usize>() + sizes.len() {
         return 0;
     }
@@ -92,7 +96,7 @@ fn try_a_lot(springs: &[Spring], sizes: &[usize], ident: usize) -> u64 {
                     0
                 }
             } else {
-                try_a_lot(&springs[index + next_size + 1..], &sizes[1..], ident + 1)
+                recurse((&springs[index + next_size + 1..], &sizes[1..]))
             }
         })
         .sum()
@@ -100,55 +104,12 @@ fn try_a_lot(springs: &[Spring], sizes: &[usize], ident: usize) -> u64 {
 
 impl Day12 {
     fn calculate_possible_arrangements(&self) -> u64 {
-        try_a_lot(&self.springs, &self.broken_lengths, 0)
-    }
-
-    fn calculate_possible_arrangements_dynamic(&self) -> u64 {
-        let mut state = HashMap::<(usize, usize, usize), u64>::new();
-
-        state.insert((0, 0, 0), 1);
-
-        for pos in 0..self.springs.len() {
-            let spring = self.springs[pos];
-
-            for groups_pos in 0..=self.broken_lengths.len() {
-                let group = self.broken_lengths.get(groups_pos).copied();
-
-                for current_len in 0..=group.unwrap_or(0) {
-                    let current = state.get(&(pos, groups_pos, current_len)).copied().unwrap_or(0);
-
-                    if current == 0 {
-                        continue;
-                    }
-
-                    if spring != Spring::Broken {
-                        let dont_have_active_group = current_len == 0;
-                        let can_end_active_group = if let Some(group) = group {
-                            current_len == group
-                        } else {
-                            false
-                        };
-
-                        if dont_have_active_group {
-                            *state.entry((pos + 1, groups_pos, 0)).or_insert(0) += current;
-                        }
-
-                        if can_end_active_group {
-                            *state.entry((pos + 1, groups_pos + 1, 0)).or_insert(0) += current;
-                        }
-                    }
-
-                    if spring != Spring::Working {
-                        *state.entry((pos + 1, groups_pos, current_len + 1)).or_insert(0) += current;
-                    }
-                }
-            }
-        }
-
-        state
-            .get(&(self.springs.len(), self.broken_lengths.len(), 0))
-            .copied()
-            .unwrap_or(0)
+        // both slices only ever shrink from the front, so their lengths identify them
+        Memo::new().call_by(
+            (&self.springs[..], &self.broken_lengths[..]),
+            &|(springs, sizes)| (springs.len(), sizes.len()),
+            &count_arrangements,
+        )
     }
 
     fn unfold(self, times: usize) -> Day12 {
@@ -186,13 +147,15 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(
         data.into_par_iter()
             .map(|day| day.unfold(5))
-            .map(|day| day.calculate_possible_arrangements_dynamic())
+            .map(|day| day.calculate_possible_arrangements())
             .sum(),
     )
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -206,4 +169,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(525152));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(Day12::parse)(input).is_ok()
+        });
+    }
 }
@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 use advent_of_code::utils::{parse_input, Parsable};
 use itertools::Itertools;
 use nom::branch::alt;
@@ -20,6 +22,15 @@ impl Parsable<'_> for Tile {
     }
 }
 
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tile::Empty => ".",
+            Tile::Wall => "#",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Pattern {
     tiles: Vec<Vec<Tile>>,
@@ -104,6 +115,13 @@ impl Parsable<'_> for Pattern {
     }
 }
 
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self.tiles.iter().map(|row| row.iter().join(""));
+        write!(f, "{}", rows.format("\n"))
+    }
+}
+
 fn parse(input: &str) -> IResult<&str, Vec<Pattern>> {
     parse_input(separated_list1(count(line_ending, 2), Pattern::parse))(input)
 }
@@ -142,6 +160,8 @@ pub fn part_two(input: &str) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -155,4 +175,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(400));
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let (_, patterns) = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        patterns.iter().for_each(advent_of_code::utils::assert_round_trip);
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
@@ -7,11 +7,14 @@ use nom::combinator::value;
 use nom::multi::many1;
 use nom::IResult;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
 use advent_of_code::utils::location::{direction, Access2d, Location};
-use advent_of_code::utils::{parse_input_by_lines, Parsable};
+use advent_of_code::utils::rng::Rng;
+use advent_of_code::utils::viz::image::{colour, Animation, Image};
+use advent_of_code::utils::{parse_input_by_lines, viz, Parsable};
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, generate);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Tile {
@@ -30,10 +33,32 @@ impl Parsable<'_> for Tile {
     }
 }
 
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tile::Empty => ".",
+            Tile::RoundedRock => "O",
+            Tile::CubeShapedRock => "#",
+        })
+    }
+}
+
 fn parse(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
     parse_input_by_lines(many1(Tile::parse))(input)
 }
 
+/// Square platform with `size` long sides, a fifth of the tiles are rounded rocks and another fifth cube-shaped ones.
+fn generate(size: usize, rng: &mut Rng) -> Result<String, String> {
+    if size == 0 {
+        return Err("the platform needs at least one tile".to_string());
+    }
+
+    let tiles = [Tile::RoundedRock, Tile::CubeShapedRock, Tile::Empty];
+    Ok((0..size)
+        .map(|_| (0..size).map(|_| tiles[rng.below(tiles.len() + 2).min(2)]).join(""))
+        .join("\n"))
+}
+
 fn find_lowest_position<T>(data: &Vec<Vec<T>>, gravity: Location<i32>) -> Vec<Location<i32>> {
     data.iter_2d_keys()
         .filter(|&location| data.get_2d(location.try_map(TryFrom::try_from).unwrap() + gravity).is_none())
@@ -79,6 +104,22 @@ fn calc_load(data: Vec<Vec<Tile>>) -> u32 {
         .sum()
 }
 
+/// One spin cycle, `on_frame` sees the platform after every tilt.
+fn spin(data: &mut Vec<Vec<Tile>>, on_frame: &mut impl FnMut(&Vec<Vec<Tile>>)) {
+    for gravity in [direction::UP, direction::LEFT, direction::DOWN, direction::RIGHT] {
+        tilt(data, gravity);
+        on_frame(data);
+    }
+}
+
+fn draw(data: &Vec<Vec<Tile>>) -> Image {
+    Image::from_grid(data, 4, |tile| match tile {
+        Tile::Empty => colour::BLACK,
+        Tile::RoundedRock => colour::YELLOW,
+        Tile::CubeShapedRock => colour::GREY,
+    })
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let (_, mut data) = parse(input).unwrap();
     tilt(&mut data, direction::UP);
@@ -88,6 +129,16 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let (_, mut data) = parse(input).unwrap();
 
+    let mut animation = viz::is_enabled().then(|| {
+        let image = draw(&data);
+        Animation::create(DAY, "spin_cycles.gif", image.width(), image.height(), 5).expect("could not create animation")
+    });
+    let mut on_frame = |data: &Vec<Vec<Tile>>| {
+        if let Some(animation) = &mut animation {
+            animation.push(&draw(data)).expect("could not write frame");
+        }
+    };
+
     let mut first_encountered = HashMap::new();
     let mut cycle_detected = None;
 
@@ -97,10 +148,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             break;
         }
 
-        tilt(&mut data, direction::UP);
-        tilt(&mut data, direction::LEFT);
-        tilt(&mut data, direction::DOWN);
-        tilt(&mut data, direction::RIGHT);
+        spin(&mut data, &mut on_frame);
     }
 
     if let Some(cycle) = cycle_detected {
@@ -115,6 +163,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -128,4 +178,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(64));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 
 use itertools::Itertools;
 use nom::branch::alt;
@@ -9,9 +10,12 @@ use nom::IResult;
 use rayon::prelude::*;
 
 use advent_of_code::utils::location::{direction, Access2d, Location};
-use advent_of_code::utils::{parse_input_by_lines, Parsable};
+use advent_of_code::utils::rng::Rng;
+use advent_of_code::utils::viz::image::{colour, Animation, Image};
+use advent_of_code::utils::viz::terminal::{Colour, Glyph, GridRenderer};
+use advent_of_code::utils::{parse_input_by_lines, viz, Parsable};
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, generate);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -34,10 +38,43 @@ impl Parsable<'_> for Tile {
     }
 }
 
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tile::Empty => ".",
+            Tile::DownwardsMirror => "\\",
+            Tile::UpwardsMirror => "/",
+            Tile::VerticalSplitter => "|",
+            Tile::HorizontalSplitter => "-",
+        })
+    }
+}
+
 fn parse(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
     parse_input_by_lines(many1(Tile::parse))(input)
 }
 
+/// Square contraption with `size` long sides, about one tile in ten is a mirror or a splitter.
+fn generate(size: usize, rng: &mut Rng) -> Result<String, String> {
+    if size == 0 {
+        return Err("the contraption needs at least one tile".to_string());
+    }
+
+    let devices = [
+        Tile::DownwardsMirror,
+        Tile::UpwardsMirror,
+        Tile::VerticalSplitter,
+        Tile::HorizontalSplitter,
+    ];
+    Ok((0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.1) { *rng.choose(&devices) } else { Tile::Empty })
+                .join("")
+        })
+        .join("\n"))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Ray {
     location: Location<i32>,
@@ -80,31 +117,79 @@ impl Ray {
     }
 }
 
-fn brute_raytrace(data: &Vec<Vec<Tile>>, starting_ray: Ray) -> usize {
+/// Every energized location, `on_step` sees the rays processed so far after every step of the beams.
+fn energized_with(
+    data: &Vec<Vec<Tile>>,
+    starting_ray: Ray,
+    mut on_step: impl FnMut(&HashSet<Ray>),
+) -> HashSet<Location<i32>> {
     let mut processed_rays = HashSet::from([]);
     let mut unprocessed_rays = vec![starting_ray];
 
-    while let Some(ray) = unprocessed_rays.pop() {
-        if let Some(tile) = data.get_2d(ray.location) {
-            if !processed_rays.insert(ray) {
-                continue;
-            }
+    while !unprocessed_rays.is_empty() {
+        let mut next_rays = vec![];
+
+        for ray in unprocessed_rays {
+            if let Some(tile) = data.get_2d(ray.location) {
+                if !processed_rays.insert(ray) {
+                    continue;
+                }
 
-            let (a, b) = ray.step(tile);
-            unprocessed_rays.push(a);
-            if let Some(b) = b {
-                unprocessed_rays.push(b);
+                let (a, b) = ray.step(tile);
+                next_rays.push(a);
+                next_rays.extend(b);
             }
         }
+
+        on_step(&processed_rays);
+        unprocessed_rays = next_rays;
     }
 
-    processed_rays.iter().map(|ray| ray.location).unique().count()
+    processed_rays.iter().map(|ray| ray.location).collect()
+}
+
+fn energized(data: &Vec<Vec<Tile>>, starting_ray: Ray) -> HashSet<Location<i32>> {
+    energized_with(data, starting_ray, |_| {})
+}
+
+/// Writes the beams spreading from `starting_ray` to `data/viz/16/beams.gif`.
+fn animate(data: &Vec<Vec<Tile>>, starting_ray: Ray) {
+    let draw = |rays: &HashSet<Ray>| {
+        let mut image = Image::from_grid(data, 4, |tile| match tile {
+            Tile::Empty => colour::BLACK,
+            _ => colour::GREY,
+        });
+        for ray in rays {
+            let loc = ray.location.map(|a| a as usize) * 4;
+            image.fill(loc + Location::new(1, 1), Location::new(2, 2), colour::YELLOW);
+        }
+        image
+    };
+
+    let empty = draw(&HashSet::new());
+    let mut animation =
+        Animation::create(DAY, "beams.gif", empty.width(), empty.height(), 3).expect("could not create animation");
+    energized_with(data, starting_ray, |rays| {
+        animation.push(&draw(rays)).expect("could not write frame");
+    });
+}
+
+fn brute_raytrace(data: &Vec<Vec<Tile>>, starting_ray: Ray) -> usize {
+    energized(data, starting_ray).len()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     let (_, data) = parse(input).unwrap();
+    let starting_ray = Ray::new(Location::new(0, 0), direction::RIGHT);
+
+    if viz::is_enabled() {
+        animate(&data, starting_ray);
 
-    Some(brute_raytrace(&data, Ray::new(Location::new(0, 0), direction::RIGHT)))
+        let renderer = GridRenderer::new(&data, Glyph::of).highlight(energized(&data, starting_ray), Colour::Yellow);
+        eprint!("{renderer}");
+    }
+
+    Some(brute_raytrace(&data, starting_ray))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -124,6 +209,8 @@ pub fn part_two(input: &str) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -137,4 +224,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(51));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
@@ -4,6 +4,7 @@ use std::hash::Hash;
 use std::ops::Add;
 use std::str::FromStr;
 
+use itertools::Itertools;
 use nom::character::complete::one_of;
 use nom::combinator::{map_res, recognize};
 use nom::multi::many1;
@@ -12,13 +13,24 @@ use num::Zero;
 
 use advent_of_code::utils::location::{direction, Access2d, Location};
 use advent_of_code::utils::parse_input_by_lines;
+use advent_of_code::utils::rng::Rng;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, generate);
 
 fn parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
     parse_input_by_lines(many1(map_res(recognize(one_of("0123456789")), i32::from_str)))(input)
 }
 
+/// Square city with `size` long sides and random heat losses between 1 and 9.
+fn generate(size: usize, rng: &mut Rng) -> Result<String, String> {
+    // the ultra crucible has to move at least 4 blocks before it can stop at the factory
+    if size < 5 {
+        return Err("the city has to be at least 5 blocks wide".to_string());
+    }
+
+    Ok((0..size).map(|_| (0..size).map(|_| rng.range(1..10)).join("")).join("\n"))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 struct Crucible {
     location: Location<i32>,
@@ -227,6 +239,8 @@ pub fn part_two(input: &str) -> Option<i32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -240,4 +254,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(94));
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
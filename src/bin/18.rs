@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take};
@@ -42,6 +44,18 @@ impl Parsable<'_> for Direction {
     }
 }
 
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct DigPlan {
     direction: Direction,
     length: i32,
@@ -79,6 +93,23 @@ impl Parsable<'_> for DigPlan {
     }
 }
 
+impl Display for DigPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let decoded_direction = match self.decoded_direction {
+            Direction::Right => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Up => 3,
+        };
+
+        write!(
+            f,
+            "{} {} (#{:05x}{decoded_direction})",
+            self.direction, self.length, self.decoded_length
+        )
+    }
+}
+
 fn parse(input: &str) -> IResult<&str, Vec<DigPlan>> {
     parse_input_by_lines(DigPlan::parse)(input)
 }
@@ -121,6 +152,8 @@ pub fn part_two(input: &str) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -134,4 +167,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(952408144115));
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let (_, plans) = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        plans.iter().for_each(advent_of_code::utils::assert_round_trip);
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::ops::{Index, IndexMut, Range};
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{line_ending, one_of};
@@ -32,6 +34,16 @@ impl Parsable<'_> for WorkflowType {
     }
 }
 
+impl Display for WorkflowType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkflowType::Accepted => write!(f, "A"),
+            WorkflowType::Rejected => write!(f, "R"),
+            WorkflowType::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Attribute {
     ExtremelyCool,
@@ -51,6 +63,17 @@ impl Parsable<'_> for Attribute {
     }
 }
 
+impl Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Attribute::ExtremelyCool => "x",
+            Attribute::Musical => "m",
+            Attribute::Aerodynamic => "a",
+            Attribute::Shiny => "s",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Condition {
     LessThan(u32),
@@ -82,6 +105,15 @@ impl Parsable<'_> for Condition {
     }
 }
 
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::LessThan(x) => write!(f, "<{x}"),
+            Condition::MoreThan(x) => write!(f, ">{x}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Rule {
     Conditional {
@@ -110,6 +142,19 @@ impl Parsable<'_> for Rule {
     }
 }
 
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::Conditional {
+                attribute,
+                condition,
+                next,
+            } => write!(f, "{attribute}{condition}:{next}"),
+            Rule::Fallback { next } => write!(f, "{next}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Workflow {
     workflow_type: WorkflowType,
@@ -182,6 +227,12 @@ impl Parsable<'_> for Workflow {
     }
 }
 
+impl Display for Workflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{{{}}}", self.workflow_type, self.rules.iter().format(","))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Part {
     coolness: u32,
@@ -384,6 +435,8 @@ pub fn part_two(input: &str) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -552,4 +605,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(167409079868000));
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let (_, (workflows, _)) = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        workflows.iter().for_each(advent_of_code::utils::assert_round_trip);
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
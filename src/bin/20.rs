@@ -1,4 +1,8 @@
-use advent_of_code::utils::{parse_input_by_lines, Parsable};
+use advent_of_code::utils::graph::{Graph, Interner};
+use advent_of_code::utils::math::first_common_time;
+use advent_of_code::utils::rng::Rng;
+use advent_of_code::utils::viz::dot::{Dot, Shape};
+use advent_of_code::utils::{parse_input_by_lines, viz, Parsable};
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -6,11 +10,13 @@ use nom::character::complete::alpha1;
 use nom::combinator::{success, value};
 use nom::multi::separated_list1;
 use nom::IResult;
-use std::collections::{HashMap, VecDeque};
-advent_of_code::solution!(20);
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+advent_of_code::solution!(20, generate);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum SwitchVariant {
+pub enum SwitchVariant {
     FlipFlop,
     Conjunction,
     Broadcast,
@@ -26,11 +32,21 @@ impl Parsable<'_> for SwitchVariant {
     }
 }
 
+impl Display for SwitchVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::FlipFlop => "%",
+            Self::Conjunction => "&",
+            Self::Broadcast => "",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Rule {
-    name: String,
-    variant: SwitchVariant,
-    targets: Vec<String>,
+pub struct Rule {
+    pub name: String,
+    pub variant: SwitchVariant,
+    pub targets: Vec<String>,
 }
 
 impl Parsable<'_> for Rule {
@@ -52,102 +68,299 @@ impl Parsable<'_> for Rule {
     }
 }
 
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{} -> {}", self.variant, self.name, self.targets.join(", "))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum State {
+pub enum State {
     FlipFlop(bool),
-    Conjunction(HashMap<String, bool>),
+    /// Last pulse remembered from every input.
+    Conjunction(HashMap<usize, bool>),
     Broadcast,
+    /// Modules that only appear as targets, like `output` or `rx`.
+    Sink,
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Rule>> {
-    parse_input_by_lines(Rule::parse)(input)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pulse {
+    pub source: usize,
+    pub target: usize,
+    pub high: bool,
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (_, rules) = parse(input).unwrap();
+/// Simulates the module network, modules are referred to by their interned ids.
+#[derive(Debug, Clone)]
+pub struct Network {
+    rules: Vec<Rule>,
+    names: Interner<String>,
+    graph: Graph,
+    /// The graph with its edges reversed, mapping every module to its inputs.
+    inputs: Graph,
+    initial: Vec<State>,
+    state: Vec<State>,
+    button: usize,
+    broadcaster: usize,
+    presses: u64,
+}
 
-    let rules = rules
-        .into_iter()
-        .map(|rule| (rule.name.clone(), rule))
-        .collect::<HashMap<_, _>>();
-
-    let inputs = rules
-        .iter()
-        .flat_map(|(_, rule)| rule.targets.iter().map(|target| (target.clone(), &rule.name)))
-        .into_group_map();
-
-    let mut state = rules
-        .iter()
-        .map(|(name, rule)| {
-            (
-                name.clone(),
-                match rule.variant {
-                    SwitchVariant::FlipFlop => State::FlipFlop(false),
-                    SwitchVariant::Conjunction => State::Conjunction(
-                        inputs
-                            .get(&rule.name)
-                            .unwrap()
-                            .iter()
-                            .map(|&name| (name.clone(), false))
-                            .collect(),
-                    ),
-                    SwitchVariant::Broadcast => State::Broadcast,
-                },
-            )
-        })
-        .collect::<HashMap<_, _>>();
+impl Network {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        let mut names = Interner::new();
+        let button = names.intern("button");
+        let broadcaster = names.intern("broadcaster");
+
+        let mut graph = Graph::directed(0);
+        for rule in &rules {
+            let from = names.intern(rule.name.as_str());
+            for target in &rule.targets {
+                graph.add_edge(from, names.intern(target.as_str()), ());
+            }
+        }
+        graph.add_edge(button, broadcaster, ());
+
+        let inputs = graph.reversed();
+        let mut initial = vec![State::Sink; names.len()];
+        for rule in &rules {
+            let id = names.get(rule.name.as_str()).unwrap();
+            initial[id] = match rule.variant {
+                SwitchVariant::FlipFlop => State::FlipFlop(false),
+                SwitchVariant::Conjunction => {
+                    State::Conjunction(inputs.neighbours(id).map(|input| (input, false)).collect())
+                }
+                SwitchVariant::Broadcast => State::Broadcast,
+            };
+        }
 
-    let mut signal_queue = VecDeque::new();
-    let mut lows = 0u64;
-    let mut highs = 0u64;
+        Self {
+            rules,
+            names,
+            graph,
+            inputs,
+            state: initial.clone(),
+            initial,
+            button,
+            broadcaster,
+            presses: 0,
+        }
+    }
 
-    for _ in 0..1000 {
-        signal_queue.push_back(("broadcaster", "button", false));
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
 
-        while let Some((current, source, signal)) = signal_queue.pop_front() {
-            *if signal { &mut highs } else { &mut lows } += 1;
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.names.get(name)
+    }
 
-            let Some(rule) = rules.get(current) else {
-                continue;
-            };
-            let Some(state) = state.get_mut(current) else {
-                continue;
+    /// Modules that send pulses to `module`.
+    pub fn inputs(&self, module: usize) -> Vec<usize> {
+        self.inputs.neighbours(module).collect()
+    }
+
+    /// Graphviz source of the network, with a shape for every kind of module.
+    pub fn to_dot(&self) -> Dot {
+        let mut dot = Dot::from_graph("network", &self.graph);
+        for (id, state) in self.initial.iter().enumerate() {
+            let shape = match state {
+                State::FlipFlop(_) => Shape::Box,
+                State::Conjunction(_) => Shape::InvTriangle,
+                State::Broadcast => Shape::DoubleCircle,
+                State::Sink => Shape::Circle,
             };
+            dot.node(id, self.names.name(id), shape);
+        }
+        dot
+    }
+
+    pub fn reset(&mut self) {
+        self.state = self.initial.clone();
+        self.presses = 0;
+    }
+
+    /// Sends a low pulse to the broadcaster and returns every pulse in the order they were processed.
+    pub fn press_button(&mut self) -> Vec<Pulse> {
+        self.presses += 1;
 
-            let emit = match state {
+        let mut pulses = vec![Pulse {
+            source: self.button,
+            target: self.broadcaster,
+            high: false,
+        }];
+        let mut next = 0;
+
+        while let Some(&Pulse { source, target, high }) = pulses.get(next) {
+            next += 1;
+
+            let emit = match &mut self.state[target] {
+                State::FlipFlop(_) if high => continue,
                 State::FlipFlop(state) => {
-                    if signal {
-                        continue;
-                    }
                     *state = !*state;
                     *state
                 }
-                State::Conjunction(state) => {
-                    let Some(con_state) = state.get_mut(source) else {
-                        continue;
-                    };
-                    *con_state = signal;
-
-                    !state.iter().all(|(_, &state)| state)
+                State::Conjunction(memory) => {
+                    memory.insert(source, high);
+                    !memory.values().all(|&high| high)
                 }
-                State::Broadcast => signal,
+                State::Broadcast => high,
+                State::Sink => continue,
             };
 
-            for target in &rule.targets {
-                signal_queue.push_back((target, current, emit));
+            pulses.extend(self.graph.neighbours(target).map(|next| Pulse {
+                source: target,
+                target: next,
+                high: emit,
+            }));
+        }
+
+        pulses
+    }
+
+    /// Presses the button `presses` times, returning the press number of every pulse arriving at `module`.
+    pub fn observe(&mut self, module: usize, presses: u64) -> Vec<(u64, Pulse)> {
+        (0..presses)
+            .flat_map(|_| {
+                let press = self.presses + 1;
+                self.press_button()
+                    .into_iter()
+                    .filter(move |pulse| pulse.target == module)
+                    .map(move |pulse| (press, pulse))
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    /// For every input of `module`, the presses during which it sends it a high pulse, as `(offset, period)`.
+    ///
+    /// Every input has to repeat with a fixed period within `max_presses`, otherwise returns `None`.
+    pub fn high_input_cycles(&mut self, module: usize, max_presses: u64) -> Option<Vec<(i64, i64)>> {
+        let inputs = self.inputs(module);
+        let mut seen = HashMap::<usize, Vec<u64>>::new();
+
+        self.reset();
+        while self.presses < max_presses
+            && !inputs
+                .iter()
+                .all(|input| seen.get(input).is_some_and(|times| times.len() >= 3))
+        {
+            for (press, pulse) in self.observe(module, 1) {
+                let times = seen.entry(pulse.source).or_default();
+                if pulse.high && times.last() != Some(&press) {
+                    times.push(press);
+                }
             }
         }
+
+        inputs
+            .iter()
+            .map(|input| {
+                let times = seen.get(input)?;
+                let (first, second, third) = (times.first()?, times.get(1)?, times.get(2)?);
+                (second - first == third - second).then_some((*first as i64, (second - first) as i64))
+            })
+            .collect()
     }
+}
+
+fn parse(input: &str) -> IResult<&str, Vec<Rule>> {
+    parse_input_by_lines(Rule::parse)(input)
+}
 
-    Some(lows * highs)
+fn rule(variant: SwitchVariant, name: &str, targets: &[String]) -> Rule {
+    Rule {
+        name: name.to_string(),
+        variant,
+        targets: targets.to_vec(),
+    }
+}
+
+/// Network shaped like the real input: the broadcaster drives binary counters made of `bits` flip-flops each, and
+/// `rx` gets a low pulse once all of them reach their period at the same time.
+///
+/// A counter resets when its conjunction hub has seen every flip-flop of a set bit of the period go high. Periods
+/// have to be odd and have their top bit set.
+fn counters(bits: usize, periods: &[u64]) -> Vec<Rule> {
+    let mut rules = vec![rule(SwitchVariant::Conjunction, "fin", &["rx".to_string()])];
+    let mut starts = vec![];
+
+    for (counter, &period) in periods.iter().enumerate() {
+        let letter = char::from(b'a' + counter as u8);
+        let flip_flops = (0..bits)
+            .map(|bit| format!("{letter}{}", char::from(b'a' + bit as u8)))
+            .collect_vec();
+        let (hub, inverter) = (format!("h{letter}"), format!("i{letter}"));
+        let is_set = |bit: usize| period >> bit & 1 == 1;
+
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let targets = flip_flops.get(bit + 1).into_iter().chain(is_set(bit).then_some(&hub));
+            rules.push(rule(SwitchVariant::FlipFlop, flip_flop, &targets.cloned().collect_vec()));
+        }
+
+        // the hub flips the unset bits on, and the lowest bit off, which carries through the whole counter
+        let mut hub_targets = (0..bits)
+            .filter(|&bit| bit == 0 || !is_set(bit))
+            .map(|bit| flip_flops[bit].clone())
+            .collect_vec();
+        hub_targets.push(inverter.clone());
+
+        rules.push(rule(SwitchVariant::Conjunction, &hub, &hub_targets));
+        rules.push(rule(SwitchVariant::Conjunction, &inverter, &["fin".to_string()]));
+        starts.push(flip_flops[0].clone());
+    }
+
+    rules.push(rule(SwitchVariant::Broadcast, "broadcaster", &starts));
+    rules
+}
+
+/// Four counters of `size` bits each, with different prime periods.
+fn generate(size: usize, rng: &mut Rng) -> Result<String, String> {
+    // the answer is the product of the periods, which has to fit into 64 bits
+    if !(5..=15).contains(&size) {
+        return Err("counters need between 5 and 15 bits".to_string());
+    }
+
+    let mut primes = (1 << (size - 1)..1 << size)
+        .filter(|&n: &u64| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect_vec();
+    rng.shuffle(&mut primes);
+
+    let mut rules = counters(size, &primes[..4]);
+    rng.shuffle(&mut rules);
+    Ok(rules.iter().join("\n"))
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (_, rules) = parse(input).unwrap();
+    let mut network = Network::new(rules);
+
+    if viz::is_enabled() {
+        viz::write(DAY, "network.dot", network.to_dot().to_string()).expect("could not write visualization");
+    }
+
+    let (highs, lows): (Vec<_>, Vec<_>) = (0..1000).flat_map(|_| network.press_button()).partition(|pulse| pulse.high);
+
+    Some(highs.len() as u64 * lows.len() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    None
+    let (_, rules) = parse(input).unwrap();
+    let mut network = Network::new(rules);
+
+    // `rx` is fed by a single conjunction, which sends a low pulse once all of its inputs send it a high one
+    let [feeder] = network.inputs(network.id("rx")?)[..] else {
+        return None;
+    };
+    let cycles = network.high_input_cycles(feeder, 100_000)?;
+
+    first_common_time(&cycles).map(|press| press as u64)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -161,4 +374,51 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_part_two_with_counters() {
+        // three 4 bit counters with periods 11, 13 and 15
+        let input = r#"
+broadcaster -> aw, bw, cw
+%aw -> ax, ha
+%ax -> ay, ha
+%ay -> az
+%az -> ha
+&ha -> ay, aw, ia
+&ia -> fin
+%bw -> bx, hb
+%bx -> by
+%by -> bz, hb
+%bz -> hb
+&hb -> bx, bw, ib
+&ib -> fin
+%cw -> cx, hc
+%cx -> cy, hc
+%cy -> cz, hc
+%cz -> hc
+&hc -> cw, ic
+&ic -> fin
+&fin -> rx
+        "#
+        .trim();
+
+        let result = part_two(input);
+        assert_eq!(result, Some(2145));
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = counters(5, &[17, 19, 23, 29]).iter().join("\n");
+        assert_eq!(part_two(&input), Some(17 * 19 * 23 * 29));
+
+        let input = generate(12, &mut Rng::new(20)).unwrap();
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
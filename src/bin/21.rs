@@ -1,43 +1,21 @@
-use std::collections::VecDeque;
-
-use advent_of_code::utils::location::{Access2d, Location};
+use advent_of_code::utils::grid::{count_reachable_with_parity, parse_grid};
+use advent_of_code::utils::location::Location;
+use advent_of_code::utils::poly::polynomial_tail;
 
 advent_of_code::solution!(21);
 
 fn count(data: &Vec<Vec<char>>, max_distance: i32, start: Location<i32>) -> i64 {
-    let mut visitable = vec![vec![None; data[0].len()]; data.len()];
-    let mut queue = VecDeque::from([(start, 0)]);
-
-    while let Some((current, distance)) = queue.pop_front() {
-        if distance > max_distance {
-            continue;
-        };
-
-        if data.get_2d(current).unwrap_or(&'#') == &'#' {
-            continue;
-        }
-        if visitable.get_2d(current).unwrap_or(&Some(false)).is_some() {
-            continue;
-        }
-
-        visitable.set_2d(current, Some(distance % 2 == max_distance % 2));
-
-        for next in current.iter_adjacent() {
-            queue.push_back((next, distance + 1));
-        }
-    }
-
-    visitable.iter().flatten().filter(|x| x.is_some_and(|a| a)).count() as i64
+    count_reachable_with_parity(data, start, max_distance as usize, |&tile| tile != '#') as i64
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
     let size = data.len() as i32;
     count(&data, 64, Location::new(size / 2, size / 2)).into()
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let data = parse_grid(input)?;
 
     // TODO: optimize
     let expansion = 7;
@@ -51,22 +29,21 @@ pub fn part_two(input: &str) -> Option<i64> {
     let size = data.len() as i32;
     let start = Location::new(size / 2, size / 2);
 
-    let zero = count(&data, 65, start);
-    let one = count(&data, 131 + 65, start);
-    let two = count(&data, 2 * 131 + 65, start);
-
-    let c = zero;
-    let a = (two - 2 * one + c) / 2;
-    let b = one - a - c;
-
-    let three = count(&data, 3 * 131 + 65, start);
-    assert_eq!(three, a * 3 * 3 + b * 3 + c);
+    // the reachable count grows quadratically with every period of 131 steps, the fourth sample confirms the fit
+    let samples = (0..4)
+        .map(|period| count(&data, period * 131 + 65, start))
+        .collect::<Vec<_>>();
 
-    Some(a * 202300 * 202300 + b * 202300 + c)
+    match polynomial_tail(&samples, 1) {
+        Some((0, polynomial)) if polynomial.degree() <= Some(2) => Some(polynomial.eval(202300)),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -80,4 +57,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            let (one, two) = (part_one(input), part_two(input));
+            one.is_some() || two.is_some()
+        });
+    }
 }
@@ -1,18 +1,19 @@
+use advent_of_code::utils::graph::Graph;
 use advent_of_code::utils::location::{Access2d, Location};
-use advent_of_code::utils::{parse_input_by_lines, Parsable};
+use advent_of_code::utils::rect::Rect;
+use advent_of_code::utils::viz::image::{colour, Animation, Image};
+use advent_of_code::utils::{parse_input_by_lines, viz, Parsable};
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::sequence::{separated_pair, tuple};
 use nom::IResult;
-use num::Bounded;
-use std::cmp::{max, min};
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 advent_of_code::solution!(22);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Brick {
-    start: Location<i32>,
-    end: Location<i32>,
+    area: Rect<i32>,
     height: i32,
     y: i32,
 }
@@ -28,8 +29,7 @@ impl Parsable<'_> for Brick {
         Ok((
             input,
             Self {
-                start: Location::new(x1, z1),
-                end: Location::new(x2 + 1, z2 + 1),
+                area: Rect::new(Location::new(x1, z1), Location::new(x2 + 1, z2 + 1)),
                 height: y2 - y1 + 1,
                 y: y1,
             },
@@ -37,26 +37,38 @@ impl Parsable<'_> for Brick {
     }
 }
 
-fn calculate_supported_by(bricks: &Vec<Brick>) -> Vec<HashSet<usize>> {
-    let mut min_loc = Location::max_value();
-    let mut max_loc = Location::min_value();
-
-    for brick in bricks {
-        min_loc.x = min(min_loc.x, brick.start.x);
-        min_loc.y = min(min_loc.y, brick.start.y);
-        max_loc.x = max(max_loc.x, brick.end.x);
-        max_loc.y = max(max_loc.y, brick.end.y);
+impl Display for Brick {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (start, end) = (self.area.start, self.area.end);
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            start.x,
+            start.y,
+            self.y,
+            end.x - 1,
+            end.y - 1,
+            self.y + self.height - 1
+        )
     }
+}
+
+/// Directed graph from every brick to the bricks it rests on.
+///
+/// Bricks settle from the lowest one up, `on_settle` gets the index of every brick and the height it lands at.
+fn calculate_supported_by(bricks: &[Brick], mut on_settle: impl FnMut(usize, i32)) -> Graph {
+    let bounds = bricks.iter().map(|brick| brick.area).reduce(Rect::union).unwrap();
+    let min_loc = bounds.start;
 
-    let mut grid = vec![vec![(0, None); (max_loc.x - min_loc.x) as usize]; (max_loc.y - min_loc.y) as usize];
+    let mut grid = vec![vec![(0, None); bounds.width() as usize]; bounds.height() as usize];
 
-    let mut supported_by: Vec<HashSet<usize>> = vec![HashSet::new(); bricks.len()];
+    let mut supported_by = Graph::directed(bricks.len());
 
     for (i, brick) in bricks.iter().enumerate().sorted_by_key(|elem| elem.1.y) {
         let mut max = 0;
         let mut supports = HashSet::new();
 
-        for loc in brick.start.iter_range(brick.end) {
+        for loc in brick.area {
             let Some((support_y, Some(support_id))) = grid.get_2d(loc - min_loc) else {
                 continue;
             };
@@ -73,24 +85,61 @@ fn calculate_supported_by(bricks: &Vec<Brick>) -> Vec<HashSet<usize>> {
             }
         }
 
-        for loc in brick.start.iter_range(brick.end) {
+        for loc in brick.area {
             grid.set_2d(loc - min_loc, (max + brick.height, Some(i)));
         }
 
-        supported_by[i] = supports;
+        for support in supports {
+            supported_by.add_edge(i, support, ());
+        }
+        on_settle(i, max);
     }
     supported_by
 }
 
+/// Writes the bricks settling, seen from the side along the x axis, to `data/viz/22/falling.gif`.
+fn animate(bricks: &[Brick]) {
+    const SCALE: usize = 4;
+
+    let mut settled = vec![];
+    calculate_supported_by(bricks, |id, bottom| settled.push((id, bottom)));
+
+    let width = bricks.iter().map(|brick| brick.area.end.x).max().unwrap_or(0) as usize;
+    let height = settled
+        .iter()
+        .map(|&(id, bottom)| bottom + bricks[id].height)
+        .max()
+        .unwrap_or(0) as usize;
+
+    let mut image = Image::new(width * SCALE, height * SCALE, colour::BLACK);
+    let mut animation =
+        Animation::create(DAY, "falling.gif", image.width(), image.height(), 2).expect("could not create animation");
+
+    for (id, bottom) in settled {
+        let brick = &bricks[id];
+        let top = height - (bottom + brick.height) as usize;
+
+        image.fill(
+            Location::new(brick.area.start.x as usize, top) * SCALE,
+            Location::new(brick.area.width() as usize, brick.height as usize) * SCALE,
+            colour::PALETTE[id % colour::PALETTE.len()],
+        );
+        animation.push(&image).expect("could not write frame");
+    }
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     let (_, bricks) = parse_input_by_lines(Brick::parse)(input).unwrap();
 
-    let supported_by = calculate_supported_by(&bricks);
+    let supported_by = calculate_supported_by(&bricks, |_, _| {});
 
-    let non_removable_count = supported_by
-        .iter()
-        .filter(|supports| supports.len() == 1)
-        .map(|supports| supports.iter().next().unwrap())
+    if viz::is_enabled() {
+        animate(&bricks);
+    }
+
+    let non_removable_count = (0..bricks.len())
+        .filter(|&i| supported_by.edges(i).len() == 1)
+        .map(|i| supported_by.edges(i)[0].0)
         .unique()
         .count();
 
@@ -100,23 +149,20 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let (_, bricks) = parse_input_by_lines(Brick::parse)(input).unwrap();
 
-    let supported_by = calculate_supported_by(&bricks);
-    let mut supports = vec![HashSet::new(); bricks.len()];
-
-    for (i, supported_by) in supported_by.iter().enumerate() {
-        for support in supported_by {
-            supports[*support].insert(i);
-        }
-    }
+    let supported_by = calculate_supported_by(&bricks, |_, _| {});
+    let supports = supported_by.reversed();
 
     let causes_to_fall = |id: usize| {
         let mut causes_to_fall = HashSet::from([id]);
-        let mut queue = Vec::from_iter(supports[id].iter());
+        let mut queue = Vec::from_iter(supports.neighbours(id));
 
         while let Some(supported_id) = queue.pop() {
-            if causes_to_fall.is_superset(&supported_by[*supported_id]) {
-                causes_to_fall.insert(*supported_id);
-                queue.extend(&supports[*supported_id]);
+            if supported_by
+                .neighbours(supported_id)
+                .all(|support| causes_to_fall.contains(&support))
+            {
+                causes_to_fall.insert(supported_id);
+                queue.extend(supports.neighbours(supported_id));
             }
         }
 
@@ -128,6 +174,8 @@ pub fn part_two(input: &str) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -141,4 +189,18 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let (_, bricks) = parse_input_by_lines(Brick::parse)(&input).unwrap();
+        bricks.iter().for_each(advent_of_code::utils::assert_round_trip);
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse_input_by_lines(Brick::parse)(input).is_ok()
+        });
+    }
 }
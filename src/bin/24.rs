@@ -1,14 +1,20 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
 use std::ops::RangeBounds;
 
+use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete::space0;
 use nom::sequence::{preceded, separated_pair, tuple};
 use nom::IResult;
+use num::{BigRational, ToPrimitive};
 
+use advent_of_code::utils::linalg::{Matrix, SolveError};
 use advent_of_code::utils::location3d::Location3d;
+use advent_of_code::utils::rng::Rng;
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(24, generate);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Hailstone {
@@ -35,12 +41,14 @@ impl Parsable<'_> for Hailstone {
     }
 }
 
-impl Hailstone {
-    fn adjust_velocity(&self, velocity: Location3d<f64>) -> Self {
-        Self {
-            position: self.position,
-            velocity: self.velocity - velocity,
-        }
+impl Display for Hailstone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (position, velocity) = exact(self);
+        write!(
+            f,
+            "{}, {}, {} @ {}, {}, {}",
+            position.x, position.y, position.z, velocity.x, velocity.y, velocity.z
+        )
     }
 }
 
@@ -80,6 +88,50 @@ fn parse(input: &str) -> IResult<&str, Vec<Hailstone>> {
     parse_input_by_lines(Hailstone::parse)(input)
 }
 
+/// Hailstones that all get hit by a rock thrown from `rock` with `velocity`, each at a different time.
+fn hailstones_hit_by(rock: Location3d<i64>, velocity: Location3d<i64>, count: usize, rng: &mut Rng) -> Vec<Hailstone> {
+    let mut times = BTreeSet::new();
+    while times.len() < count {
+        times.insert(rng.range(100_000_000_000..1_000_000_000_000));
+    }
+
+    let mut hailstones = times
+        .into_iter()
+        .map(|time| {
+            // hailstones moving along an axis as fast as the rock would never meet it there
+            let mut axis = || loop {
+                let speed = rng.range(-500..500);
+                if speed != 0 {
+                    break speed;
+                }
+            };
+            let hailstone_velocity = Location3d::new(axis(), axis(), axis()) + velocity;
+            let position = rock + (velocity - hailstone_velocity) * time;
+
+            Hailstone {
+                position: position.map(|a| a as f64),
+                velocity: hailstone_velocity.map(|a| a as f64),
+            }
+        })
+        .collect_vec();
+
+    rng.shuffle(&mut hailstones);
+    hailstones
+}
+
+/// `size` hailstones, placed like in the real input so that a single rock hits them all.
+fn generate(size: usize, rng: &mut Rng) -> Result<String, String> {
+    if size < 3 {
+        return Err("at least 3 hailstones are needed to find the rock".to_string());
+    }
+
+    let area = 200_000_000_000_000..400_000_000_000_000;
+    let rock = Location3d::new(rng.range(area.clone()), rng.range(area.clone()), rng.range(area));
+    let velocity = Location3d::new(rng.range(-300..300), rng.range(-300..300), rng.range(-300..300));
+
+    Ok(hailstones_hit_by(rock, velocity, size, rng).iter().join("\n"))
+}
+
 fn is_in_square_2d(range: impl RangeBounds<f64>) -> impl Fn(&Location3d<f64>) -> bool {
     move |pos| range.contains(&pos.x) && range.contains(&pos.y)
 }
@@ -93,107 +145,77 @@ pub fn part_one(input: &str) -> Option<usize> {
     ))
 }
 
-fn find_one_intersection_point_2d(hailstones: impl Iterator<Item = Hailstone>) -> Option<Location3d<f64>> {
-    let mut intersection_point = None;
-    let mut iter = hailstones.into_iter();
-    let check_stone = iter.next()?;
-
-    for hailstone in iter {
-        let Some(intersection) = intersect(&check_stone, &hailstone) else {
-            return None;
-        };
-
-        match intersection_point {
-            None => intersection_point = Some(intersection),
-            Some(point) if point.to_2d() != intersection.to_2d() => return None,
-            _ => {}
-        }
-    }
-
-    intersection_point
+/// Exact integer position and velocity, part one's floats are only approximations of these.
+fn exact(hailstone: &Hailstone) -> (Location3d<i128>, Location3d<i128>) {
+    (hailstone.position.map(|a| a as i128), hailstone.velocity.map(|a| a as i128))
 }
 
-fn find_one_intersection_point_2d_with_velocity(
-    hailstones: &[Hailstone],
-    velocity: Location3d<f64>,
-) -> Option<Location3d<f64>> {
-    find_one_intersection_point_2d(hailstones.iter().map(|hailstone| hailstone.adjust_velocity(velocity)))
+fn cross(a: Location3d<i128>, b: Location3d<i128>) -> Location3d<i128> {
+    Location3d::new(a.y * b.z - a.z * b.y, a.z * b.x - a.x * b.z, a.x * b.y - a.y * b.x)
 }
 
-fn find_vz_from_xy_velocity_and_intersection(
-    hailstones: &[Hailstone],
-    intersection: &Location3d<f64>,
-    velocity: &Location3d<f64>,
-) -> Option<f64> {
-    let mut vz = None;
-    let mut iter = hailstones.iter().map(|hailstone| hailstone.adjust_velocity(*velocity));
+/// The rock `(P, V)` hits hailstone `i` iff `(P - p_i) x (V - v_i) = 0`. The `P x V` term is shared by every hailstone,
+/// so subtracting the equations of two hailstones leaves 3 linear ones:
+/// `P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i`.
+fn rock_equations(first: &Hailstone, second: &Hailstone) -> Vec<(Vec<i128>, i128)> {
+    let (p_i, v_i) = exact(first);
+    let (p_j, v_j) = exact(second);
+    let d = v_j - v_i;
+    let e = p_j - p_i;
+    let rhs = cross(p_j, v_j) - cross(p_i, v_i);
+
+    // unknowns are [P.x, P.y, P.z, V.x, V.y, V.z]
+    vec![
+        (vec![0, d.z, -d.y, 0, -e.z, e.y], rhs.x),
+        (vec![-d.z, 0, d.x, e.z, 0, -e.x], rhs.y),
+        (vec![d.y, -d.x, 0, -e.y, e.x, 0], rhs.z),
+    ]
+}
 
-    let check_stone = iter.next()?;
-    let check_time = get_time_2d(&check_stone, intersection);
-    let check_z = check_stone.position.z + check_time * check_stone.velocity.z;
+/// Solves for the rock from 3 hailstones, fails if they don't determine it uniquely.
+fn find_rock(first: &Hailstone, second: &Hailstone, third: &Hailstone) -> Result<Vec<BigRational>, SolveError> {
+    let (matrix, rhs): (Vec<_>, Vec<_>) = rock_equations(first, second)
+        .into_iter()
+        .chain(rock_equations(first, third))
+        .unzip();
 
-    for hailstone in iter {
-        let time = get_time_2d(&hailstone, intersection);
-        let z = hailstone.position.z + time * hailstone.velocity.z;
+    let rhs = rhs.into_iter().map(|a| BigRational::from_integer(a.into())).collect_vec();
+    Matrix::from_integers(&matrix).solve(&rhs)
+}
 
-        if time == check_time {
-            continue;
-        }
+/// Tries consecutive triples of hailstones until one determines the rock, returns the error of the last one otherwise.
+fn find_rock_in(hailstones: &[Hailstone]) -> Result<Vec<BigRational>, SolveError> {
+    let mut result = Err(SolveError::Underdetermined);
 
-        let new_vz = (z - check_z) / (time - check_time);
-        match vz {
-            None => vz = Some(new_vz),
-            Some(old_vz) if old_vz != new_vz => return None,
-            _ => {}
+    for (first, second, third) in hailstones.iter().tuple_windows() {
+        result = find_rock(first, second, third);
+        if result.is_ok() {
+            break;
         }
     }
 
-    vz
-}
-
-fn get_time_2d(hailstone: &Hailstone, intersection: &Location3d<f64>) -> f64 {
-    if hailstone.velocity.x == 0.0 {
-        return (intersection.y - hailstone.position.y) / hailstone.velocity.y;
-    }
-    (intersection.x - hailstone.position.x) / hailstone.velocity.x
+    result
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
     let (_, hailstones) = parse(input).unwrap();
 
-    for x in 0..=500 {
-        for y in 0..=500 {
-            for velocity in [
-                Location3d::new(x, y, 0),
-                Location3d::new(-x, y, 0),
-                Location3d::new(x, -y, 0),
-                Location3d::new(-x, -y, 0),
-            ]
-            .iter()
-            {
-                let velocity = velocity.map(|a| a as f64);
-                let Some(intersection) = find_one_intersection_point_2d_with_velocity(&hailstones, velocity) else {
-                    continue;
-                };
-
-                let Some(vz) = find_vz_from_xy_velocity_and_intersection(&hailstones, &intersection, &velocity) else {
-                    continue;
-                };
-
-                // println!("Found vz {}", vz);
-                let z = hailstones[0].position.z
-                    + get_time_2d(&hailstones[0].adjust_velocity(velocity), &intersection) * (hailstones[0].velocity.z - vz);
-
-                return Some(intersection.x as i64 + intersection.y as i64 + z as i64);
-            }
+    let rock = match find_rock_in(&hailstones) {
+        Ok(rock) => rock,
+        Err(e) => {
+            eprintln!("No triple of hailstones determines the rock: {e}");
+            return None;
         }
-    }
+    };
 
-    None
+    let sum = &rock[0] + &rock[1] + &rock[2];
+    sum.is_integer().then(|| sum.to_integer().to_i64())?
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -207,4 +229,42 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(47));
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let (_, hailstones) = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        hailstones.iter().for_each(advent_of_code::utils::assert_round_trip);
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let mut rng = Rng::new(24);
+        let rock = Location3d::new(250_000_000_000_000, 300_000_000_000_000, 350_000_000_000_000);
+        let hailstones = hailstones_hit_by(rock, Location3d::new(-20, 100, 7), 300, &mut rng);
+
+        let result = part_two(&hailstones.iter().join("\n"));
+        assert_eq!(result, Some(900_000_000_000_000));
+        assert!(generate(2, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_degenerate_hailstones() {
+        let (_, parallel) = parse("0, 0, 0 @ 1, 1, 1\n1, 0, 0 @ 1, 1, 1\n0, 2, 0 @ 1, 1, 1").unwrap();
+        assert!(find_rock_in(&parallel).is_err());
+        assert_eq!(part_two(&parallel.iter().join("\n")), None);
+
+        // the first two triples repeat a hailstone, the third one determines the rock
+        let (_, mut hailstones) = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        hailstones.insert(0, hailstones[0]);
+        hailstones.insert(0, hailstones[0]);
+        assert!(find_rock(&hailstones[0], &hailstones[1], &hailstones[2]).is_err());
+        assert_eq!(part_two(&hailstones.iter().join("\n")), Some(47));
+    }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
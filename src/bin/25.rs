@@ -1,11 +1,10 @@
-use advent_of_code::utils::{parse_input_by_lines, Parsable};
-use itertools::Itertools;
+use advent_of_code::utils::graph::{Graph, Interner, MinCut};
+use advent_of_code::utils::viz::dot::{Dot, Shape};
+use advent_of_code::utils::{parse_input_by_lines, viz, Parsable};
 use nom::bytes::complete::tag;
 use nom::character::complete::alpha1;
 use nom::multi::separated_list1;
 use nom::IResult;
-use rand::prelude::SliceRandom;
-use std::collections::HashSet;
 advent_of_code::solution!(25);
 
 struct Node {
@@ -33,101 +32,41 @@ fn parse(input: &str) -> IResult<&str, Vec<Node>> {
     parse_input_by_lines(Node::parse)(input)
 }
 
-#[derive(Debug, Clone)]
-struct Edge {
-    from: String,
-    to: String,
-}
-
-impl Edge {
-    fn new(from: String, to: String) -> Self {
-        Self { from, to }
+/// Writes the wiring to `data/viz/25/wiring.dot` when running with `--viz`, the two halves of the cut get different shapes.
+fn visualize(names: &Interner<String>, graph: &Graph<u32>, cut: &MinCut<u32>) {
+    if !viz::is_enabled() {
+        return;
     }
-}
 
-fn try_min_cut(edges: &[Edge]) -> Option<(usize, HashSet<String>, HashSet<String>)> {
-    let nodes = edges
-        .iter()
-        .flat_map(|edge| vec![&edge.from, &edge.to])
-        .unique()
-        .collect::<Vec<_>>();
-
-    let mut node_count = nodes.len();
-    let mut current_edges = edges.to_vec();
-
-    let mut chosen = vec![];
-    let mut rng = rand::thread_rng();
-
-    while node_count != 2 {
-        let edge = current_edges.choose(&mut rng).unwrap();
-        chosen.push(edge.clone());
-
-        let convert = |name: &str| {
-            if name == edge.to {
-                edge.from.clone()
-            } else {
-                name.to_string()
-            }
-        };
-
-        current_edges = current_edges
-            .iter()
-            .map(|e| Edge::new(convert(&e.from), convert(&e.to)))
-            .filter(|e| e.from != e.to)
-            .collect::<Vec<_>>();
-        node_count -= 1;
+    let mut dot = Dot::from_graph("wiring", graph);
+    for &node in &cut.left {
+        dot.node(node, names.name(node), Shape::Box);
     }
-
-    let [a, b] = current_edges
-        .iter()
-        .flat_map(|edge| vec![&edge.from, &edge.to])
-        .unique()
-        .collect::<Vec<_>>()[..]
-    else {
-        unreachable!("Didn't end up with 2 nodes in graph");
-    };
-
-    let mut left = HashSet::from([a.to_string()]);
-    let mut right = HashSet::from([b.to_string()]);
-
-    for edge in chosen.iter().rev() {
-        if left.contains(&edge.from) {
-            left.insert(edge.to.to_string());
-        } else if right.contains(&edge.from) {
-            right.insert(edge.to.to_string());
-        } else {
-            unreachable!("Node not in either side of the cut")
-        }
+    for &node in &cut.right {
+        dot.node(node, names.name(node), Shape::Ellipse);
     }
 
-    println!("Current edges: {:?}", current_edges);
-    println!("Left size: {:?}, Right size: {:?}", left.len(), right.len());
-
-    Some((current_edges.len(), left, right))
+    viz::write(DAY, "wiring.dot", dot.to_string()).expect("could not write visualization");
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     let (_, nodes) = parse(input).unwrap();
-    let edges = nodes
-        .iter()
-        .flat_map(|node| {
-            node.neighbors
-                .iter()
-                .map(move |neighbor| Edge::new(node.name.clone(), neighbor.clone()))
-        })
-        .collect::<Vec<_>>();
-
-    loop {
-        let Some((result, left, right)) = try_min_cut(&edges) else {
-            return None;
-        };
-
-        println!("Result: {}", result);
-
-        if result == 3 {
-            return Some(left.len() * right.len());
+
+    let mut names = Interner::<String>::new();
+    let mut graph = Graph::undirected(0);
+
+    for node in &nodes {
+        let from = names.intern(node.name.as_str());
+        for neighbor in &node.neighbors {
+            graph.add_edge(from, names.intern(neighbor.as_str()), 1u32);
         }
     }
+
+    let cut = graph.min_cut()?;
+    assert_eq!(cut.weight, 3, "Expected the wiring to be cut by exactly 3 wires");
+    visualize(&names, &graph, &cut);
+
+    Some(cut.left.len() * cut.right.len())
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
@@ -136,6 +75,8 @@ pub fn part_two(_input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::utils::fuzz::fuzz_parser;
+
     use super::*;
 
     #[test]
@@ -149,4 +90,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_parser_fuzz() {
+        fuzz_parser(&advent_of_code::template::read_file("examples", DAY), |input| {
+            parse(input).is_ok()
+        });
+    }
 }
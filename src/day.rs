@@ -114,6 +114,96 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, e.g. to pick the days `cargo all` runs.
+///
+/// # Parsing
+/// A set parses from a comma separated list of days and inclusive ranges, e.g. `1-10,17,20-25`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates a set of every day of advent.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    /// Creates a set of the days that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates the days of the set in order.
+    pub fn iter(self) -> impl Iterator<Item = Day> {
+        all_days().filter(move |day| self.contains(*day))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::default();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::default();
+
+        for item in s.split(',').map(str::trim) {
+            let error = || DaySetFromStrError(item.to_string());
+
+            let (start, end): (Result<Day, _>, Result<Day, _>) = match item.split_once('-') {
+                Some((start, end)) => (start.trim().parse(), end.trim().parse()),
+                None => (item.parse(), item.parse()),
+            };
+            let (Ok(start), Ok(end)) = (start, end) else {
+                return Err(error());
+            };
+            if start > end {
+                return Err(error());
+            }
+
+            (start.0..=end.0).for_each(|day| set.insert(Day(day)));
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day or range `{}`, expecting days between 1 and 25 like `1-10,17,20-25`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +224,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +257,31 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn day_set_from_str() {
+        let set: DaySet = "1-10, 17,20-25".parse().unwrap();
+        let days: Vec<u8> = set.iter().map(Day::into_inner).collect();
+        assert_eq!(days, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 17, 20, 21, 22, 23, 24, 25]);
+
+        assert_eq!("23".parse::<DaySet>().unwrap().iter().collect::<Vec<_>>(), [Day(23)]);
+        assert_eq!("1-25".parse::<DaySet>().unwrap(), DaySet::all());
+
+        for invalid in ["", "0", "26", "1-26", "10-1", "1,,2", "a-b", "1-2-3"] {
+            assert!(invalid.parse::<DaySet>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn day_set_difference() {
+        let set = DaySet::all().difference("2-24".parse().unwrap());
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Day(1), Day(25)]);
+        assert!(set.contains(Day(25)));
+        assert!(!set.contains(Day(23)));
+        assert!(DaySet::default().is_empty());
+        assert!(!set.is_empty());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -1,17 +1,27 @@
 #![feature(if_let_guard)]
 
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, bench, download, generate, read, scaffold, solve};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::commands::{all, bench, solve};
     use advent_of_code::Day;
 
     pub enum AppArguments {
+        BenchCompare {
+            rev: Option<String>,
+            threshold: f64,
+        },
         Download {
             day: Day,
         },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: u64,
+        },
         Read {
             day: Day,
         },
@@ -20,13 +30,10 @@ mod args {
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: solve::Options,
         },
         All {
-            release: bool,
-            time: bool,
+            options: all::Options,
         },
     }
 
@@ -35,12 +42,36 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                options: all::Options {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    generated: args.contains("--generated"),
+                    timeout: args.opt_value_from_str("--timeout")?,
+                    alloc: args.contains("--alloc"),
+                    days: args.opt_value_from_str("--days")?,
+                    exclude: args.opt_value_from_str("--exclude")?,
+                    only_solved: args.contains("--only-solved"),
+                    skip_slow: args.opt_value_from_str("--skip-slow")?,
+                },
+            },
+            Some("bench") => match args.subcommand()?.as_deref() {
+                Some("compare") => AppArguments::BenchCompare {
+                    threshold: args.opt_value_from_str("--threshold")?.unwrap_or(bench::DEFAULT_THRESHOLD),
+                    rev: args.opt_free_from_str()?,
+                },
+                _ => {
+                    eprintln!("Unknown bench command. Format: cargo run -- bench compare [REV] [--threshold 5]");
+                    process::exit(1);
+                }
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("generate") => AppArguments::Generate {
+                day: args.free_from_str()?,
+                size: args.value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(1),
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
@@ -49,9 +80,23 @@ mod args {
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                options: solve::Options {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    viz: args.contains("--viz"),
+                    generated: args.contains("--generated"),
+                    input: args.opt_value_from_str("--input")?,
+                    part: args.opt_value_from_str("--part")?,
+                    timeout: args.opt_value_from_str("--timeout")?,
+                    alloc: args.contains("--alloc"),
+                    // the example number is optional, so it's read last when every other argument is consumed.
+                    example: if args.contains("--example") {
+                        Some(args.opt_free_from_str()?.unwrap_or(1))
+                    } else {
+                        None
+                    },
+                },
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -79,16 +124,13 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All { options } => all::handle(&options),
+            AppArguments::BenchCompare { rev, threshold } => bench::handle_compare(rev, threshold),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Generate { day, size, seed } => generate::handle(day, size, seed),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            AppArguments::Solve { day, options } => solve::handle(day, &options),
         },
     };
 }
@@ -0,0 +1,154 @@
+/// Counting global allocator, used to report memory usage of solutions.
+///
+/// It's only installed with the `alloc_stats` feature, so normal runs don't pay for the bookkeeping. The `solve` and
+/// `all` commands enable the feature for the solutions when they get the `--alloc` flag.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_deallocation(size: usize) {
+    LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // counted as a new allocation, growing a `Vec` is what makes most of them
+            record_deallocation(layout.size());
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made while running a piece of code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// Most memory in use at once, on top of what was in use before.
+    pub peak_bytes: u64,
+}
+
+/// Whether the counting allocator is installed.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc_stats")
+}
+
+/// Runs `f` and counts its allocations. Allocations of other threads running at the same time are counted too.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(live_bytes),
+    };
+
+    (result, stats)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", units[unit])
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} allocated in {} allocs",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.allocated_bytes),
+            self.allocations
+        )
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::AllocStats;
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            allocated_bytes: 3 * 1024 * 1024 + 512 * 1024,
+            peak_bytes: 100,
+        };
+        assert_eq!(stats.to_string(), "100 B peak, 3.5 MiB allocated in 12 allocs");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc_stats")]
+    fn counts_allocations() {
+        let (_, stats) = super::measure(|| {
+            let first = vec![0u8; 1000];
+            drop(first);
+            vec![0u8; 500]
+        });
+
+        // the counters are global, tests running on other threads at the same time can only add to them.
+        assert!(stats.allocations >= 2);
+        assert!(stats.allocated_bytes >= 1500);
+        assert!(stats.peak_bytes >= 1000);
+    }
+}
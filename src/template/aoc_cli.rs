@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::{data_dir, get_data_path};
 use crate::Day;
 
 #[derive(Debug)]
@@ -83,11 +84,11 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    get_data_path("inputs", day).display().to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    data_dir().join("puzzles").join(format!("{day}.md")).display().to_string()
 }
 
 fn get_year() -> Option<u16> {
@@ -0,0 +1,404 @@
+/// Module that keeps every timed release run in an append-only file, so that the timings of different commits can be
+/// compared. The README table only ever shows the latest run.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::{alloc, data_dir};
+use crate::Day;
+
+static HEADER: &str = "commit,date,machine,day,part,mean_ns,stddev_ns,samples";
+
+/// Two-sided 95% critical values of Student's t-distribution for 1 to 30 degrees of freedom.
+const T_CRITICAL: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110,
+    2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+];
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(message) => f.write_str(message),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The benchmark of one part, i.e. one line of the history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Hash of the checked out commit, with a `-dirty` suffix if the solutions had uncommitted changes.
+    pub commit: String,
+    /// UTC time of the run, e.g. `2023-12-17T08:30:00Z`.
+    pub date: String,
+    pub machine: String,
+    pub day: Day,
+    pub part: u8,
+    pub mean_nanos: f64,
+    pub stddev_nanos: f64,
+    pub samples: u64,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{:.0},{:.0},{}",
+            self.commit, self.date, self.machine, self.day, self.part, self.mean_nanos, self.stddev_nanos, self.samples
+        )
+    }
+}
+
+impl FromStr for Entry {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let error = || Error::Parser(format!("Malformed benchmark history line: {line}"));
+        let fields: Vec<&str> = line.trim().split(',').collect();
+
+        let [commit, date, machine, day, part, mean, stddev, samples] = fields[..] else {
+            return Err(error());
+        };
+
+        Ok(Entry {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            machine: machine.to_string(),
+            day: day.parse().map_err(|_| error())?,
+            part: part.parse().map_err(|_| error())?,
+            mean_nanos: mean.parse().map_err(|_| error())?,
+            stddev_nanos: stddev.parse().map_err(|_| error())?,
+            samples: samples.parse().map_err(|_| error())?,
+        })
+    }
+}
+
+#[must_use]
+pub fn get_path() -> PathBuf {
+    data_dir().join("benchmarks.csv")
+}
+
+/// Whether the current run belongs in the history: a benched release build on the real input. Debug builds, examples,
+/// generated inputs and the counting allocator all give timings that can't be compared with the others.
+#[must_use]
+pub fn is_recorded() -> bool {
+    let args: Vec<String> = env::args().collect();
+    let has = |flag: &str| args.iter().any(|x| x == flag);
+
+    !cfg!(debug_assertions)
+        && !alloc::is_enabled()
+        && has("--time")
+        && !has("--example")
+        && !has("--generated")
+        && !has("--input")
+}
+
+/// Appends the benchmark of a part to the history, creating the file if needed.
+pub fn record(day: Day, part: u8, mean: Duration, stddev: Duration, samples: u128) -> Result<(), Error> {
+    let entry = Entry {
+        commit: current_commit().unwrap_or_else(|| "unknown".into()),
+        date: format_date(SystemTime::now()),
+        machine: machine_id(),
+        day,
+        part,
+        mean_nanos: mean.as_secs_f64() * 1e9,
+        stddev_nanos: stddev.as_secs_f64() * 1e9,
+        samples: u64::try_from(samples).unwrap_or(u64::MAX),
+    };
+
+    let path = get_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{HEADER}")?;
+    }
+    writeln!(file, "{entry}")?;
+    Ok(())
+}
+
+/// Reads all entries of the history, oldest first.
+pub fn load() -> Result<Vec<Entry>, Error> {
+    let content = match fs::read_to_string(get_path()) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && *line != HEADER)
+        .map(str::parse)
+        .collect()
+}
+
+/// Time of every day in milliseconds, from the latest benchmarks of its parts on `machine`.
+#[must_use]
+pub fn latest_millis(entries: &[Entry], machine: &str) -> BTreeMap<Day, f64> {
+    let latest: BTreeMap<(Day, u8), f64> = entries
+        .iter()
+        .filter(|entry| entry.machine == machine)
+        .map(|entry| ((entry.day, entry.part), entry.mean_nanos))
+        .collect();
+
+    let mut days = BTreeMap::new();
+    for ((day, _), nanos) in latest {
+        *days.entry(day).or_default() += nanos / 1_000_000_f64;
+    }
+    days
+}
+
+/// Identifies the machine a benchmark ran on: `$AOC_MACHINE_ID` if set, the host name otherwise.
+#[must_use]
+pub fn machine_id() -> String {
+    let id = env::var("AOC_MACHINE_ID")
+        .ok()
+        .or_else(|| command_output(&mut Command::new("hostname")))
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .unwrap_or_else(|| "unknown".into());
+
+    // the history is comma separated.
+    id.trim().replace([',', '\n', '\r'], "_")
+}
+
+/// Hash of `HEAD`, suffixed with `-dirty` if there are uncommitted changes to the solutions or the template.
+/// The history and the README are left out, they change with every timed run.
+fn current_commit() -> Option<String> {
+    let hash = resolve_commit("HEAD")?;
+    let status = command_output(Command::new("git").args(["status", "--porcelain", "--", "src", "Cargo.toml"]))?;
+
+    if status.is_empty() {
+        Some(hash)
+    } else {
+        Some(format!("{hash}-dirty"))
+    }
+}
+
+/// Full hash of a git revision, e.g. `HEAD~1` or a branch name.
+#[must_use]
+pub fn resolve_commit(rev: &str) -> Option<String> {
+    command_output(Command::new("git").args(["rev-parse", "--verify", "--quiet", &format!("{rev}^{{commit}}")]))
+}
+
+fn command_output(cmd: &mut Command) -> Option<String> {
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Formats a point in time as UTC, e.g. `2023-12-17T08:30:00Z`.
+fn format_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, secs) = (secs / 86400, secs % 86400);
+
+    // civil date from days since epoch, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Change of a part's timing between two benchmarks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub baseline: Entry,
+    pub current: Entry,
+}
+
+impl Comparison {
+    /// Relative change of the mean, e.g. `0.1` if the part got 10% slower.
+    #[must_use]
+    pub fn change(&self) -> f64 {
+        self.current.mean_nanos / self.baseline.mean_nanos - 1.0
+    }
+
+    /// Whether the difference of the means is statistically significant (Welch's t-test at 95%).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn is_significant(&self) -> bool {
+        let (a, b) = (&self.baseline, &self.current);
+        if a.samples < 2 || b.samples < 2 {
+            return false;
+        }
+
+        let var_a = a.stddev_nanos.powi(2) / a.samples as f64;
+        let var_b = b.stddev_nanos.powi(2) / b.samples as f64;
+        let std_err = (var_a + var_b).sqrt();
+
+        if std_err == 0.0 {
+            return a.mean_nanos != b.mean_nanos;
+        }
+
+        let t = (a.mean_nanos - b.mean_nanos).abs() / std_err;
+        let freedom =
+            (var_a + var_b).powi(2) / (var_a.powi(2) / (a.samples - 1) as f64 + var_b.powi(2) / (b.samples - 1) as f64);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let critical = T_CRITICAL.get((freedom.floor() as usize).max(1) - 1).copied().unwrap_or(1.96);
+
+        t > critical
+    }
+
+    /// A significant slowdown of more than `threshold`, e.g. `0.05` for 5%.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.is_significant() && self.change() > threshold
+    }
+}
+
+/// Pairs the latest benchmark of every part at `baseline_commit` with the latest benchmark of the part recorded after
+/// it, on the same machine. Parts without a newer benchmark are left out.
+#[must_use]
+pub fn compare(entries: &[Entry], baseline_commit: &str, machine: &str) -> Vec<Comparison> {
+    let mut baselines: BTreeMap<(Day, u8), &Entry> = BTreeMap::new();
+    let mut currents: BTreeMap<(Day, u8), &Entry> = BTreeMap::new();
+
+    for entry in entries.iter().filter(|entry| entry.machine == machine) {
+        let key = (entry.day, entry.part);
+
+        if entry.commit == baseline_commit {
+            baselines.insert(key, entry);
+            currents.remove(&key);
+        } else if baselines.contains_key(&key) {
+            currents.insert(key, entry);
+        }
+    }
+
+    baselines
+        .into_iter()
+        .filter_map(|(key, baseline)| {
+            Some(Comparison {
+                baseline: baseline.clone(),
+                current: (*currents.get(&key)?).clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{compare, format_date, latest_millis, Entry};
+    use crate::day;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn entry(commit: &str, day: u8, mean_nanos: f64, stddev_nanos: f64) -> Entry {
+        Entry {
+            commit: commit.into(),
+            date: "2023-12-17T08:30:00Z".into(),
+            machine: "box".into(),
+            day: crate::Day::new(day).unwrap(),
+            part: 1,
+            mean_nanos,
+            stddev_nanos,
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn entries_roundtrip() {
+        let entry = entry("abc123-dirty", 17, 1_500_000.0, 2_000.0);
+        let line = entry.to_string();
+
+        assert_eq!(line, "abc123-dirty,2023-12-17T08:30:00Z,box,17,1,1500000,2000,100");
+        assert_eq!(line.parse::<Entry>().unwrap(), entry);
+        assert!("abc,2023-12-17T08:30:00Z,box,26,1,1,1,1".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let time = UNIX_EPOCH + Duration::from_secs(1_702_801_800);
+        assert_eq!(format_date(time), "2023-12-17T08:30:00Z");
+        let time = UNIX_EPOCH + Duration::from_secs(951_782_400);
+        assert_eq!(format_date(time), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn compares_latest_entries() {
+        let entries = vec![
+            entry("old", 1, 1000.0, 10.0),
+            entry("base", 1, 1000.0, 10.0),
+            entry("base", 2, 1000.0, 10.0),
+            entry("new", 1, 900.0, 10.0),
+            entry("new", 1, 1500.0, 10.0),
+            Entry {
+                machine: "other".into(),
+                ..entry("newer", 1, 100.0, 10.0)
+            },
+        ];
+
+        let comparisons = compare(&entries, "base", "box");
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline.day, day!(1));
+        assert!((comparisons[0].change() - 0.5).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(0.05));
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let comparisons = compare(
+            &[entry("base", 1, 1000.0, 200.0), entry("new", 1, 1010.0, 200.0)],
+            "base",
+            "box",
+        );
+        assert!(!comparisons[0].is_significant());
+
+        let comparisons = compare(&[entry("base", 1, 1000.0, 10.0), entry("new", 1, 980.0, 10.0)], "base", "box");
+        assert!(comparisons[0].is_significant());
+        assert!(!comparisons[0].is_regression(0.05));
+    }
+
+    #[test]
+    fn sums_latest_parts() {
+        let mut second_part = entry("base", 1, 2_000_000.0, 10.0);
+        second_part.part = 2;
+        let entries = vec![
+            entry("old", 1, 9_000_000.0, 10.0),
+            entry("base", 1, 1_000_000.0, 10.0),
+            second_part,
+            Entry {
+                machine: "other".into(),
+                ..entry("base", 2, 1_000_000.0, 10.0)
+            },
+        ];
+
+        let millis = latest_millis(&entries, "box");
+        assert_eq!(millis.len(), 1);
+        assert!((millis[&day!(1)] - 3.0).abs() < 1e-9);
+    }
+}
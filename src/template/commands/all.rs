@@ -1,43 +1,242 @@
+use std::collections::BTreeMap;
 use std::io;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
 
 use crate::template::{
+    bench_history,
+    commands::generate::get_path_for_generated,
     readme_benchmarks::{self, Timings},
+    runner::DEFAULT_TIMEOUT,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet};
+
+/// Flags of `cargo all`, passed on to every solution.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub generated: bool,
+    pub timeout: Option<u64>,
+    pub alloc: bool,
+    /// Days to run, all of them if not set.
+    pub days: Option<DaySet>,
+    pub exclude: Option<DaySet>,
+    /// Leave out days that have no solution yet, instead of listing them as not solved.
+    pub only_solved: bool,
+    /// Leave out days that took longer than this many milliseconds in their latest benchmark.
+    pub skip_slow: Option<f64>,
+}
+
+impl Options {
+    /// Days picked with `--days` and `--exclude`.
+    fn picked_days(&self) -> DaySet {
+        self.days
+            .unwrap_or_else(DaySet::all)
+            .difference(self.exclude.unwrap_or_default())
+    }
+}
+
+pub fn handle(options: &Options) {
+    let picked = options.picked_days();
+    let days: Vec<Day> = picked
+        .iter()
+        .filter(|day| !options.only_solved || Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No days to run.");
+        return;
+    }
+
+    let slow_days = slow_days(options);
+
+    if !options.time {
+        // without timings, the days don't get in each other's way.
+        run_parallel(&days, options, &slow_days);
+        return;
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    for (index, day) in days.iter().enumerate() {
+        if index > 0 {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        if let Some(val) = run_day(*day, options, slow_days.get(day), &mut Log::default()) {
+            timings.push(val);
+        }
+    }
+
+    let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if output.is_empty() {
-            println!("Not solved.");
+    // timings of generated inputs aren't comparable with the real ones.
+    if options.release && !options.generated {
+        // a run of some of the days keeps the rows of the others.
+        // the counting allocator makes the timings a bit higher, so only the memory columns are written.
+        let result = if options.alloc {
+            readme_benchmarks::merge_memory(timings)
+        } else if picked == DaySet::all() && slow_days.is_empty() {
+            readme_benchmarks::update(timings, total_millis)
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            readme_benchmarks::merge(timings)
+        };
+
+        match result {
+            Ok(()) => println!("Successfully updated README with benchmarks."),
+            Err(_) => {
+                eprintln!("Failed to update readme with benchmarks.");
+            }
         }
+    }
+}
+
+/// Days to skip because of `--skip-slow`, with their time in milliseconds.
+fn slow_days(options: &Options) -> BTreeMap<Day, f64> {
+    let Some(limit) = options.skip_slow else {
+        return BTreeMap::new();
+    };
+
+    let entries = bench_history::load().unwrap_or_else(|e| {
+        eprintln!("Failed to read the benchmark history, no day is skipped: {e}");
+        vec![]
     });
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    let mut millis = bench_history::latest_millis(&entries, &bench_history::machine_id());
+    millis.retain(|_, millis| *millis > limit);
+    millis
+}
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+/// Runs several days at once on a rayon thread pool. Each day's output is held back until the days before it are
+/// printed, so that it reads the same as a sequential run.
+///
+/// Many solutions use rayon themselves, so only half as many days as there are cores run at once. The timeout of the
+/// parts grows with the number of days that share the machine.
+fn run_parallel(days: &[Day], options: &Options, slow_days: &BTreeMap<Day, f64>) {
+    let days_at_once = thread::available_parallelism().map_or(1, |cores| (cores.get() / 2).max(1));
+    let pool = ThreadPoolBuilder::new().num_threads(days_at_once).build().unwrap();
+
+    let options = &Options {
+        timeout: scale_timeout(options.timeout, days_at_once),
+        ..options.clone()
+    };
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+    // build once up front, rather than having every day wait for the lock on the build directory.
+    child_commands::build_solutions(options);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut pending = BTreeMap::new();
+    let mut next = days.iter().peekable();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            pool.install(|| {
+                days.par_iter().for_each_with(sender, |sender, day| {
+                    let mut log = Log::buffered();
+                    run_day(*day, options, slow_days.get(day), &mut log);
+                    sender.send((*day, log)).unwrap();
+                });
+            });
+        });
+
+        for (day, log) in receiver {
+            pending.insert(day, log);
+
+            while let Some(log) = next.peek().and_then(|day| pending.remove(*day)) {
+                if next.next() != days.first() {
+                    println!();
                 }
+                log.flush();
+            }
+        }
+    });
+}
+
+/// Timeout in seconds for a part that shares the machine with `days_at_once - 1` other days. `--timeout 0` stays off.
+fn scale_timeout(timeout: Option<u64>, days_at_once: usize) -> Option<u64> {
+    match timeout {
+        Some(0) => Some(0),
+        timeout => {
+            let seconds = timeout.unwrap_or(DEFAULT_TIMEOUT.as_secs());
+            Some(seconds * u64::try_from(days_at_once).unwrap_or(u64::MAX))
+        }
+    }
+}
+
+/// Runs the solution of a day and prints its output. Returns the timings if the day is solved.
+fn run_day(day: Day, options: &Options, slow_millis: Option<&f64>, log: &mut Log) -> Option<Timings> {
+    log.print(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+    log.print("------".into());
+
+    if let Some(millis) = slow_millis {
+        log.print(format!("Skipped, took {millis:.1}ms in the latest benchmark."));
+        return None;
+    }
+
+    if options.generated && !get_path_for_generated(day).exists() {
+        log.print("No generated input.".into());
+        return None;
+    }
+
+    let output = child_commands::run_solution(day, options, log).unwrap();
+
+    if output.is_empty() {
+        log.print("Not solved.".into());
+        None
+    } else {
+        Some(child_commands::parse_exec_time(&output, day))
+    }
+}
+
+enum Line {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Output of a day. Printed right away, unless it's buffered to be printed in order with the other days.
+#[derive(Default)]
+struct Log {
+    is_buffered: bool,
+    lines: Vec<Line>,
+}
+
+impl Log {
+    fn buffered() -> Self {
+        Log {
+            is_buffered: true,
+            lines: vec![],
+        }
+    }
+
+    fn print(&mut self, line: String) {
+        if self.is_buffered {
+            self.lines.push(Line::Stdout(line));
+        } else {
+            println!("{line}");
+        }
+    }
+
+    fn eprint(&mut self, line: String) {
+        if self.is_buffered {
+            self.lines.push(Line::Stderr(line));
+        } else {
+            eprintln!("{line}");
+        }
+    }
+
+    fn flush(self) {
+        for line in self.lines {
+            match line {
+                Line::Stdout(line) => println!("{line}"),
+                Line::Stderr(line) => eprintln!("{line}"),
             }
         }
     }
@@ -64,7 +263,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{get_path_for_bin, Error, Log, Options};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -73,8 +272,34 @@ mod child_commands {
         thread,
     };
 
+    /// Cargo flags that select the build of the solutions.
+    fn build_args(options: &Options) -> Vec<&'static str> {
+        let mut args = vec![];
+
+        if options.release {
+            args.push("--release");
+        }
+
+        if options.alloc {
+            args.push("--features");
+            args.push("alloc_stats");
+        }
+
+        args
+    }
+
+    /// Builds all solution bins. Failures are left to the runs of the single days to report.
+    pub fn build_solutions(options: &Options) {
+        let _ = Command::new("cargo")
+            .args(["build", "--quiet", "--bins"])
+            .args(build_args(options))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(day: Day, options: &Options, log: &mut Log) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -83,16 +308,24 @@ mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
-            args.push("--release");
-        }
+        args.extend(build_args(options));
+        args.push("--");
 
-        if is_timed {
+        if options.time {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if options.generated {
+            args.push("--generated");
+        }
+
+        let timeout = options.timeout.map(|timeout| timeout.to_string());
+        if let Some(timeout) = &timeout {
+            args.push("--timeout");
+            args.push(timeout);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -107,19 +340,25 @@ mod child_commands {
 
         let mut output = vec![];
 
+        // a buffered log gets the stderr lines after the stdout ones, there's no telling how they interleaved.
+        let mut stderr_log = Log {
+            is_buffered: log.is_buffered,
+            lines: vec![],
+        };
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                stderr_log.eprint(line.unwrap());
             });
+            stderr_log
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            log.print(line.clone());
             output.push(line);
         }
 
-        thread.join().unwrap();
+        log.lines.extend(thread.join().unwrap().lines);
         cmd.wait()?;
 
         Ok(output)
@@ -130,6 +369,8 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            memory_1: None,
+            memory_2: None,
             total_nanos: 0_f64,
         };
 
@@ -146,13 +387,15 @@ mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_allocs(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, allocs)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.memory_1 = allocs.map(Into::into);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.memory_2 = allocs.map(Into::into);
                 }
 
                 timings.total_nanos += nanos;
@@ -161,12 +404,7 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -176,16 +414,17 @@ mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = super::readme_benchmarks::parse_duration(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
 
+    /// allocation stats are only printed with `--alloc`, e.g. `(1.2ms @ 10 samples) [1.0 KiB peak, ...]`.
+    fn parse_allocs(line: &str) -> Option<&str> {
+        let (_, stats) = line.split_once(" samples) [")?;
+        stats.split(']').next()
+    }
+
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -250,5 +489,31 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_alloc_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [1.5 KiB peak, 2.0 KiB allocated in 3 allocs]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.memory_1.unwrap(), "1.5 KiB peak, 2.0 KiB allocated in 3 allocs");
+            assert!(res.memory_2.is_none());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[test]
+    fn test_scaled_timeout() {
+        assert_eq!(super::scale_timeout(None, 4), Some(240));
+        assert_eq!(super::scale_timeout(Some(10), 2), Some(20));
+        assert_eq!(super::scale_timeout(Some(0), 4), Some(0));
     }
 }
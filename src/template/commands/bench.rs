@@ -0,0 +1,79 @@
+use std::process;
+use std::time::Duration;
+
+use crate::template::bench_history::{self, Comparison};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Regression in percent that makes `bench compare` fail if `--threshold` isn't passed.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// Compares the benchmarks recorded at `rev` with the ones recorded after them on this machine. Exits with an error if
+/// a part got significantly slower by more than `threshold` percent.
+pub fn handle_compare(rev: Option<String>, threshold: f64) {
+    let rev = rev.unwrap_or_else(|| "HEAD".into());
+
+    let Some(commit) = bench_history::resolve_commit(&rev) else {
+        eprintln!("Unknown git revision: {rev}");
+        process::exit(1);
+    };
+
+    let entries = bench_history::load().unwrap_or_else(|e| {
+        eprintln!("Failed to read \"{}\": {e}", bench_history::get_path().display());
+        process::exit(1);
+    });
+
+    let machine = bench_history::machine_id();
+    let comparisons = bench_history::compare(&entries, &commit, &machine);
+    let short = &commit[..commit.len().min(8)];
+
+    if comparisons.is_empty() {
+        eprintln!(
+            "No benchmarks of {short} ({rev}) with newer ones on \"{machine}\". Run \"cargo time\" at both commits first."
+        );
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}Benchmarks since {short} ({rev}) on {machine}{ANSI_RESET}");
+    println!("------");
+
+    let threshold = threshold / 100.0;
+    let mut regressions = 0;
+
+    for comparison in &comparisons {
+        if comparison.is_regression(threshold) {
+            regressions += 1;
+        }
+        println!("{}", format_comparison(comparison, threshold));
+    }
+
+    println!();
+    if regressions > 0 {
+        println!(
+            "{ANSI_BOLD}{regressions} part(s) got slower by more than {:.1}%.{ANSI_RESET}",
+            threshold * 100.0
+        );
+        process::exit(1);
+    }
+    println!("No regressions.");
+}
+
+fn format_comparison(comparison: &Comparison, threshold: f64) -> String {
+    let change = comparison.change();
+
+    let verdict = match (comparison.is_significant(), change > 0.0) {
+        (false, _) => format!("{ANSI_ITALIC}no significant change{ANSI_RESET}"),
+        (true, true) if change > threshold => format!("{ANSI_BOLD}regression{ANSI_RESET}"),
+        (true, true) => "slower".into(),
+        (true, false) if -change > threshold => format!("{ANSI_BOLD}improvement{ANSI_RESET}"),
+        (true, false) => "faster".into(),
+    };
+
+    format!(
+        "Day {} part {}: {:.1?} -> {:.1?} ({:+.1}%) {verdict}",
+        comparison.current.day,
+        comparison.current.part,
+        Duration::from_secs_f64(comparison.baseline.mean_nanos / 1e9),
+        Duration::from_secs_f64(comparison.current.mean_nanos / 1e9),
+        change * 100.0
+    )
+}
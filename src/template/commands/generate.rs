@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use crate::template::get_data_path;
+use crate::Day;
+
+/// Where `solve --generated` and `all --generated` read the generated input from.
+#[must_use]
+pub fn get_path_for_generated(day: Day) -> PathBuf {
+    get_data_path("generated", day)
+}
+
+pub fn handle(day: Day, size: usize, seed: u64) {
+    let size = size.to_string();
+    let seed = seed.to_string();
+    let day_padded = day.to_string();
+
+    // generators live next to the solutions, so the day's bin does the work.
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day_padded])
+        .args(["--", "--generate", &size, "--seed", &seed])
+        .stderr(Stdio::inherit())
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output.stdout,
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to run the solution for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let path = get_path_for_generated(day);
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, output));
+
+    match written {
+        Ok(()) => println!("Generated input of size {size} with seed {seed} at \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write the generated input: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod all;
+pub mod bench;
 pub mod download;
+pub mod generate;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
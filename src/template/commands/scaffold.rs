@@ -4,6 +4,7 @@ use std::{
     process,
 };
 
+use crate::template::get_data_path;
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -43,8 +44,8 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+    let input_path = get_data_path("inputs", day).display().to_string();
+    let example_path = get_data_path("examples", day).display().to_string();
     let module_path = format!("src/bin/{day}.rs");
 
     let mut file = match safe_create_file(&module_path) {
@@ -2,24 +2,72 @@ use std::process::{Command, Stdio};
 
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Flags of `cargo solve`, passed on to the solution.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub submit: Option<u8>,
+    pub viz: bool,
+    pub generated: bool,
+    pub input: Option<String>,
+    pub part: Option<u8>,
+    pub timeout: Option<u64>,
+    pub alloc: bool,
+    pub example: Option<u8>,
+}
+
+pub fn handle(day: Day, options: &Options) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.alloc {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc_stats".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if options.time {
         cmd_args.push("--time".to_string());
     }
 
+    if options.viz {
+        cmd_args.push("--viz".to_string());
+    }
+
+    if options.generated {
+        cmd_args.push("--generated".to_string());
+    }
+
+    if let Some(input) = &options.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.clone());
+    }
+
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(timeout) = options.timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.to_string());
+    }
+
+    if let Some(example) = options.example {
+        cmd_args.push("--example".to_string());
+        cmd_args.push(example.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +75,10 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // e.g. a wrong answer for an example
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
 }
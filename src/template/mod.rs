@@ -1,7 +1,10 @@
 use crate::Day;
-use std::{env, fs};
+use std::path::PathBuf;
+use std::{env, fs, io, process};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod bench_history;
 pub mod commands;
 pub mod readme_benchmarks;
 pub mod runner;
@@ -10,27 +13,133 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Root of the data folders: `$AOC_DATA_DIR` if set, `data` in the working directory otherwise.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    env::var_os("AOC_DATA_DIR").map_or_else(|| PathBuf::from("data"), PathBuf::from)
+}
+
+/// Path of the `{day}.txt` file in one of the data folders, e.g. `inputs` or `examples`.
+#[must_use]
+pub fn get_data_path(folder: &str, day: Day) -> PathBuf {
+    data_dir().join(folder).join(format!("{day}.txt"))
+}
+
+/// Example selected with `--example [N]`, just `--example` selects the first one.
+#[must_use]
+pub fn selected_example() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--example")?;
+
+    match args.get(index + 1).map(|n| n.parse::<u8>()) {
+        Some(Ok(0)) => {
+            eprintln!("Examples are numbered from 1.");
+            process::exit(1);
+        }
+        Some(Ok(n)) => Some(n),
+        _ => Some(1),
+    }
+}
+
+/// Path of a file of the `n`-th example of a day: `examples/{day}.{extension}` for the first one,
+/// `examples/{day}-{n}.{extension}` for the others.
+#[must_use]
+pub fn get_example_path(day: Day, n: u8, extension: &str) -> PathBuf {
+    let name = if n == 1 { day.to_string() } else { format!("{day}-{n}") };
+    data_dir().join("examples").join(format!("{name}.{extension}"))
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = get_data_path(folder, day);
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
+/// Reads the input to solve: the file passed with `--input <path>` (`-` for stdin), an example with `--example [N]`,
+/// the generated input with `--generated`, or the downloaded input. Exits with a hint on how to get the input if it's
+/// missing.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let input = args.iter().position(|x| x == "--input").map(|index| args.get(index + 1));
+
+    let result = match (input, selected_example()) {
+        (Some(Some(path)), _) if path == "-" => {
+            io::read_to_string(io::stdin()).map_err(|e| format!("Could not read the input from stdin: {e}"))
+        }
+        (Some(Some(path)), _) => {
+            fs::read_to_string(path).map_err(|e| format!("Could not read the input file \"{path}\": {e}"))
+        }
+        (Some(None), _) => Err("Unexpected command-line input. Format: cargo solve 1 --input path/to/input.txt".to_string()),
+        (None, Some(n)) => {
+            let path = get_example_path(day, n, "txt");
+            fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read example {n} of day {day} at \"{}\": {e}", path.display()))
+        }
+        (None, None) => {
+            let generated = args.iter().any(|x| x == "--generated");
+            let folder = if generated { "generated" } else { "inputs" };
+            let path = get_data_path(folder, day);
+
+            fs::read_to_string(&path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound if generated => format!(
+                    "No generated input for day {day} at \"{}\". Try running \"cargo generate {day} --size <size>\" first.",
+                    path.display()
+                ),
+                io::ErrorKind::NotFound => format!(
+                    "No input for day {day} at \"{}\". Try running \"cargo download {day}\" first, or pass a file with \"--input <path>\".",
+                    path.display()
+                ),
+                _ => format!("Could not read the input file \"{}\": {e}", path.display()),
+            })
+        }
+    };
+
+    result.unwrap_or_else(|message| {
+        eprintln!("{message}");
+        process::exit(1);
+    })
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Days that can generate synthetic inputs pass their generator as a second argument, see
+/// [`runner::Generator`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@main $day:expr, $generator:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            if generate_input(DAY, $generator) {
+                return;
+            }
+
+            // parts run on their own threads, which need the input for the rest of the program.
+            let input: &'static str = advent_of_code::template::read_input(DAY).leak();
+            let mut is_correct = true;
+
+            if is_part_selected(1) {
+                is_correct &= run_part(part_one, input, DAY, 1);
+            }
+            if is_part_selected(2) {
+                is_correct &= run_part(part_two, input, DAY, 2);
+            }
+
+            if !is_correct {
+                std::process::exit(1);
+            }
         }
     };
+    ($day:expr) => {
+        advent_of_code::solution!(@main $day, None);
+    };
+    ($day:expr, $generator:expr) => {
+        advent_of_code::solution!(@main $day, Some($generator));
+    };
 }
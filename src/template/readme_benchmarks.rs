@@ -1,5 +1,6 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::collections::BTreeMap;
 use std::{fs, io};
 
 use crate::Day;
@@ -23,6 +24,9 @@ pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Allocation stats per part, only collected with `--alloc`.
+    pub memory_1: Option<String>,
+    pub memory_2: Option<String>,
     pub total_nanos: f64,
 }
 
@@ -60,24 +64,37 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_memory = timings
+        .iter()
+        .any(|timing| timing.memory_1.is_some() || timing.memory_2.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if has_memory {
+            line.push_str(&format!(
+                " `{}` | `{}` |",
+                timing.memory_1.unwrap_or_else(|| "-".into()),
+                timing.memory_2.unwrap_or_else(|| "-".into())
+            ));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -87,6 +104,90 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
+/// Parses a duration as printed by the runner, e.g. `74.13ms`, to nanoseconds.
+#[must_use]
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Reads a row of an existing table back, e.g. `| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |`.
+fn parse_row(line: &str) -> Option<Timings> {
+    let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+    let day = cells.get(1)?.strip_prefix("[Day ")?.split(']').next()?.parse().ok()?;
+
+    let cell = |index: usize| {
+        cells
+            .get(index)
+            .map(|cell| cell.trim_matches('`'))
+            .filter(|cell| !cell.is_empty() && *cell != "-")
+            .map(String::from)
+    };
+
+    let (part_1, part_2) = (cell(2), cell(3));
+    let total_nanos = [&part_1, &part_2]
+        .into_iter()
+        .filter_map(|part| part.as_deref().and_then(parse_duration))
+        .sum();
+
+    Some(Timings {
+        day,
+        part_1,
+        part_2,
+        memory_1: cell(4),
+        memory_2: cell(5),
+        total_nanos,
+    })
+}
+
+fn merge_content(s: &mut String, timings: Vec<Timings>) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    let mut rows: BTreeMap<Day, Timings> = s[positions.pos_start..positions.pos_end]
+        .lines()
+        .filter_map(parse_row)
+        .map(|timing| (timing.day, timing))
+        .collect();
+
+    rows.extend(timings.into_iter().map(|timing| (timing.day, timing)));
+
+    let total_millis = rows.values().map(|timing| timing.total_nanos).sum::<f64>() / 1_000_000_f64;
+    update_content(s, rows.into_values().collect(), total_millis)
+}
+
+fn merge_memory_content(s: &mut String, timings: Vec<Timings>) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    let mut rows: BTreeMap<Day, Timings> = s[positions.pos_start..positions.pos_end]
+        .lines()
+        .filter_map(parse_row)
+        .map(|timing| (timing.day, timing))
+        .collect();
+
+    for timing in timings {
+        let row = rows.entry(timing.day).or_insert_with(|| Timings {
+            day: timing.day,
+            part_1: None,
+            part_2: None,
+            memory_1: None,
+            memory_2: None,
+            total_nanos: 0.0,
+        });
+        row.memory_1 = timing.memory_1;
+        row.memory_2 = timing.memory_2;
+    }
+
+    let total_millis = rows.values().map(|timing| timing.total_nanos).sum::<f64>() / 1_000_000_f64;
+    update_content(s, rows.into_values().collect(), total_millis)
+}
+
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -102,9 +203,29 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     Ok(())
 }
 
+/// Like [`update`], but keeps the rows of the days that aren't in `timings`, for runs of only some of the days.
+/// The total is summed up again from all rows.
+pub fn merge(timings: Vec<Timings>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    merge_content(&mut readme, timings)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/// Writes only the memory columns of `timings` and keeps the durations of the existing rows, as the timings of a run
+/// with the counting allocator are a bit off.
+pub fn merge_memory(timings: Vec<Timings>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    merge_memory_content(&mut readme, timings)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{merge_content, merge_memory_content, update_content, Timings, MARKER};
     use crate::day;
 
     fn get_mock_timings() -> Vec<Timings> {
@@ -113,18 +234,24 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                memory_1: None,
+                memory_2: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                memory_1: None,
+                memory_2: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                memory_1: None,
+                memory_2: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -183,4 +310,66 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].memory_1 = Some("1.0 KiB peak, 2.0 KiB allocated in 3 allocs".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.0 KiB peak, 2.0 KiB allocated in 3 allocs` | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn merges_benchmarks() {
+        let mut timings = get_mock_timings();
+        for (timing, total_nanos) in timings.iter_mut().zip([3e+7, 7e+7, 9e+7]) {
+            timing.total_nanos = total_nanos;
+        }
+
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings.clone(), 190.0).unwrap();
+
+        timings.truncate(2);
+        timings[1].part_1 = Some("1s".into());
+        timings[1].total_nanos = 1.04e9;
+        timings[0].day = day!(3);
+        merge_content(&mut s, timings).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `1s` | `40ms` |"));
+        assert!(s.contains("| [Day 3](./src/bin/03.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |"));
+        assert!(s.contains("**Total: 1190.00ms**"));
+        assert_eq!(s.matches("[Day ").count(), 4);
+    }
+
+    #[test]
+    fn merges_only_memory() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let before = s.clone();
+
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_1 = Some("15ms".into());
+        timings[0].memory_1 = Some("1.0 KiB peak, 2.0 KiB allocated in 3 allocs".into());
+        timings[0].total_nanos = 3.5e7;
+        merge_memory_content(&mut s, timings).unwrap();
+
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.0 KiB peak, 2.0 KiB allocated in 3 allocs` | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+        assert_eq!(
+            s.lines().find(|line| line.starts_with("**Total")),
+            before.lines().find(|line| line.starts_with("**Total"))
+        );
+    }
 }
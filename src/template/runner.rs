@@ -1,51 +1,253 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::{aoc_cli, bench_history, get_example_path, selected_example, ANSI_ITALIC, ANSI_RESET};
+use crate::utils::rng::Rng;
 use crate::Day;
+use std::any::Any;
 use std::fmt::Display;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Whether `part` should run, i.e. `--part` wasn't used to select the other one.
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--part") else {
+        return true;
+    };
+
+    match args.get(index + 1).map(|x| x.parse::<u8>()) {
+        Some(Ok(selected @ (1 | 2))) => selected == part,
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 2");
+            process::exit(1);
+        }
+    }
+}
+
+/// Time a part may take when `--timeout <seconds>` isn't passed.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Stack of the thread a part runs on. Larger than the 8 MiB of the main thread, so that deeply recursive solutions
+/// don't overflow. It's only reserved, memory gets used as the stack grows.
+const PART_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Set once a part timed out. Its thread keeps running, and competes with the parts that run after it.
+static HAS_TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+/// How the first run of a part ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Solved(Option<T>),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+/// Wall-clock time a part may take, set with `--timeout <seconds>`. `--timeout 0` lets parts run forever.
+fn timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--timeout") else {
+        return Some(DEFAULT_TIMEOUT);
+    };
+
+    match args.get(index + 1).map(|x| x.parse::<u64>()) {
+        Some(Ok(0)) => None,
+        Some(Ok(seconds)) => Some(Duration::from_secs(seconds)),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 60");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs and prints one part. Returns `false` if it panicked, timed out or gave a different answer than expected
+/// for the example.
+pub fn run_part<I, T, F>(func: F, input: I, day: Day, part: u8) -> bool
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+    F: Fn(I) -> Option<T> + Copy + Send + 'static,
+{
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let is_disturbed = HAS_TIMED_OUT.load(Ordering::Relaxed);
+    if is_disturbed {
+        eprintln!("{part_str}: a part that timed out is still running, the timing of this one isn't reliable.");
+    }
+
+    let (outcome, timing, allocs) = run_timed(func, input, |outcome| print_result(outcome, &part_str, ""));
+
+    let mut stats_str = format_duration(&timing.mean, timing.samples);
+    if alloc::is_enabled() && matches!(outcome, Outcome::Solved(_)) {
+        stats_str.push_str(&format!(" [{allocs}]"));
+    }
+
+    print_result(&outcome, &part_str, &stats_str);
+
+    if timing.samples > 1 && !is_disturbed && bench_history::is_recorded() {
+        if let Err(e) = bench_history::record(day, part, timing.mean, timing.stddev, timing.samples) {
+            eprintln!(
+                "Failed to record the benchmark in \"{}\": {e}",
+                bench_history::get_path().display()
+            );
+        }
+    }
+
+    let Outcome::Solved(result) = outcome else {
+        return false;
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let is_correct = check_example_answer(result.as_ref(), day, part);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
+
+    is_correct
+}
+
+/// Runs the part on its own thread, so that a panic or a timeout doesn't take the remaining parts down with it.
+///
+/// A part that times out is left running in the background, there's no way to stop a thread from outside. The parts
+/// after it get a warning that their timings aren't reliable.
+fn run_isolated<I, T, F>(func: F, input: I) -> (Outcome<T>, AllocStats)
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> Option<T> + Send + 'static,
+{
+    let receiver = match spawn_part(move || alloc::measure(|| func(input))) {
+        Ok(receiver) => receiver,
+        Err(message) => return (Outcome::Panicked(message), AllocStats::default()),
+    };
+
+    let limit = timeout();
+    let result = match limit {
+        Some(limit) => receiver.recv_timeout(limit),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    match result {
+        Ok(Ok((result, allocs))) => (Outcome::Solved(result), allocs),
+        Ok(Err(message)) => (Outcome::Panicked(message), AllocStats::default()),
+        Err(RecvTimeoutError::Timeout) => {
+            HAS_TIMED_OUT.store(true, Ordering::Relaxed);
+            (Outcome::TimedOut(limit.unwrap_or_default()), AllocStats::default())
+        }
+        Err(RecvTimeoutError::Disconnected) => (
+            Outcome::Panicked("the part stopped without an answer".to_string()),
+            AllocStats::default(),
+        ),
+    }
+}
+
+/// Runs `f` on a new thread with a stack of [`PART_STACK_SIZE`], and sends back its result or its panic message.
+fn spawn_part<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> Result<Receiver<Result<R, String>>, String> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+            let _ = sender.send(result);
+        })
+        .map_err(|e| format!("could not start the thread of the part: {e}"))?;
+
+    Ok(receiver)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
+}
+
+/// Expected answer of a part for the example selected with `--example`.
+///
+/// Answers are kept next to the example, e.g. `data/examples/06.answers` holds lines like `Part 1: 288`.
+fn expected_example_answer(day: Day, part: u8) -> Option<String> {
+    let answers = fs::read_to_string(get_example_path(day, selected_example()?, "answers")).ok()?;
+    let prefix = format!("Part {part}:");
+
+    answers
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|answer| answer.trim().to_string())
+}
+
+fn check_example_answer<T: Display>(result: Option<&T>, day: Day, part: u8) -> bool {
+    let Some(expected) = expected_example_answer(day, part) else {
+        return true;
+    };
+
+    if result.is_some_and(|result| result.to_string() == expected) {
+        println!("  {ANSI_ITALIC}matches the example answer{ANSI_RESET}");
+        true
+    } else {
+        println!("  {ANSI_BOLD}expected {expected}{ANSI_RESET} for the example");
+        false
+    }
+}
+
+/// Execution time of a part, averaged over all samples if it was benched.
+struct Timing {
+    mean: Duration,
+    stddev: Duration,
+    samples: u128,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// Only the first execution has a timeout, parts that panicked or timed out aren't benched. All runs happen on a thread
+/// with a large stack.
+fn run_timed<I, T, F>(func: F, input: I, hook: impl Fn(&Outcome<T>)) -> (Outcome<T>, Timing, AllocStats)
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> Option<T> + Copy + Send + 'static,
+{
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (outcome, allocs) = run_isolated(func, input.clone());
     let base_time = timer.elapsed();
 
-    hook(&result);
+    hook(&outcome);
+
+    let is_solved = matches!(outcome, Outcome::Solved(_));
+    let single_run = Timing {
+        mean: base_time,
+        stddev: Duration::ZERO,
+        samples: 1,
+    };
+
+    let timing = if is_solved && std::env::args().any(|x| x == "--time") {
+        // the samples need the same stack as the first run.
+        let benched = spawn_part(move || bench(func, input, &base_time))
+            .and_then(|receiver| receiver.recv().map_err(|_| "the benchmark stopped".to_string())?);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        benched.unwrap_or_else(|message| {
+            eprintln!("Could not bench the part: {message}");
+            single_run
+        })
     } else {
-        (base_time, 1)
+        single_run
     };
 
-    (result, run.0, run.1)
+    (outcome, timing, allocs)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Timing {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -69,11 +271,14 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_possible_truncation)]
+    let mean = Duration::from_nanos(average_duration(&timers) as u64);
+
+    Timing {
+        mean,
+        stddev: standard_deviation(&timers, &mean),
+        samples: bench_iterations,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,6 +289,17 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+#[allow(clippy::cast_precision_loss)]
+fn standard_deviation(numbers: &[Duration], mean: &Duration) -> Duration {
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+        .sum::<f64>()
+        / (numbers.len().max(2) - 1) as f64;
+
+    Duration::from_secs_f64(variance.sqrt())
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -92,9 +308,23 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(outcome: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    let result = match outcome {
+        Outcome::Solved(result) => result,
+        // there's nothing to show until the part has finished
+        _ if is_intermediate_result => return,
+        Outcome::Panicked(message) => {
+            println!("{part}: {ANSI_BOLD}PANIC{ANSI_RESET} {message}");
+            return;
+        }
+        Outcome::TimedOut(limit) => {
+            println!("{part}: {ANSI_BOLD}TIMEOUT{ANSI_RESET} after {limit:.0?}");
+            return;
+        }
+    };
+
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
@@ -127,6 +357,47 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// Produces a valid puzzle input of the given size, or explains why it can't.
+///
+/// What the size means is up to the day, e.g. the side of a grid or the number of lines.
+pub type Generator = fn(usize, &mut Rng) -> Result<String, String>;
+
+/// Prints a generated input instead of solving if the bin was called with `--generate <size> [--seed <seed>]`.
+/// Returns whether it did.
+pub fn generate_input(day: Day, generator: Option<Generator>) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--generate") else {
+        return false;
+    };
+
+    let Some(generator) = generator else {
+        eprintln!("Day {day} has no input generator.");
+        process::exit(1);
+    };
+
+    let size = args.get(index + 1).and_then(|size| size.parse::<usize>().ok());
+    let seed = match args.iter().position(|x| x == "--seed") {
+        None => Some(1),
+        Some(index) => args.get(index + 1).and_then(|seed| seed.parse::<u64>().ok()),
+    };
+
+    let (Some(size), Some(seed)) = (size, seed) else {
+        eprintln!("Unexpected command-line input. Format: cargo generate 1 --size 100 --seed 1");
+        process::exit(1);
+    };
+
+    match generator(size, &mut Rng::new(seed)) {
+        Ok(input) => print!("{input}"),
+        Err(err) => {
+            eprintln!("Could not generate an input for day {day}: {err}");
+            process::exit(1);
+        }
+    }
+
+    true
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
@@ -157,6 +428,17 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if selected_example().is_some() {
+        eprintln!("Answers of examples are never submitted.");
+        return None;
+    }
+
+    // a wrong answer costs an attempt, so only answers for the downloaded input are sent.
+    if args.iter().any(|x| x == "--input" || x == "--generated") {
+        eprintln!("Answers of other or generated inputs are never submitted.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -165,3 +447,33 @@ fn submit_result<T: Display>(
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{run_isolated, spawn_part, Outcome};
+
+    #[test]
+    fn isolates_panics() {
+        let (solved, _) = run_isolated(|input: &'static str| input.parse::<u32>().ok(), "42");
+        assert_eq!(solved, Outcome::Solved(Some(42)));
+
+        let (panicked, _) = run_isolated(|input: &'static str| Some(input.parse::<u32>().unwrap()), "x");
+        assert!(matches!(panicked, Outcome::Panicked(message) if message.contains("InvalidDigit")));
+    }
+
+    #[test]
+    fn parts_get_a_large_stack() {
+        // about 32 MiB of stack, four times what the main thread has.
+        fn depth(n: u32) -> u32 {
+            let frame = std::hint::black_box([n; 256]);
+            if n == 0 {
+                0
+            } else {
+                1 + depth(n - 1) + frame[0] - frame[1]
+            }
+        }
+
+        let result = spawn_part(|| depth(32 * 1024)).unwrap().recv().unwrap();
+        assert_eq!(result, Ok(32 * 1024));
+    }
+}
@@ -0,0 +1,170 @@
+use num::{one, Num};
+
+use crate::utils::location3d::{CubeIterator, Location3d};
+use crate::utils::rect::{partial_max, partial_min, Rect};
+
+/// Axis-aligned box spanning `start` (inclusive) to `end` (exclusive), same as [`Location3d::iter_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T: Num> {
+    pub start: Location3d<T>,
+    pub end: Location3d<T>,
+}
+
+impl<T: Num> Cuboid<T> {
+    pub const fn new(start: Location3d<T>, end: Location3d<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Cuboid<T> {
+    /// Smallest box containing every point, or `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Location3d<T>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|point| Cuboid::new(point, point + Location3d::new(one(), one(), one())))
+            .reduce(Cuboid::union)
+    }
+
+    pub fn size(&self) -> Location3d<T> {
+        (self.end - self.start).map(|a| partial_max(a, T::zero()))
+    }
+
+    pub fn volume(&self) -> T {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.x >= self.end.x || self.start.y >= self.end.y || self.start.z >= self.end.z
+    }
+
+    /// Projection onto the xy plane.
+    pub fn to_2d(&self) -> Rect<T> {
+        Rect::new(self.start.to_2d(), self.end.to_2d())
+    }
+
+    pub fn contains(&self, loc: Location3d<T>) -> bool {
+        self.start.x <= loc.x
+            && loc.x < self.end.x
+            && self.start.y <= loc.y
+            && loc.y < self.end.y
+            && self.start.z <= loc.z
+            && loc.z < self.end.z
+    }
+
+    pub fn contains_cuboid(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (self.start.x <= other.start.x
+                && other.end.x <= self.end.x
+                && self.start.y <= other.start.y
+                && other.end.y <= self.end.y
+                && self.start.z <= other.start.z
+                && other.end.z <= self.end.z)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Overlapping part of both boxes, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Cuboid::new(
+            Location3d::new(
+                partial_max(self.start.x, other.start.x),
+                partial_max(self.start.y, other.start.y),
+                partial_max(self.start.z, other.start.z),
+            ),
+            Location3d::new(
+                partial_min(self.end.x, other.end.x),
+                partial_min(self.end.y, other.end.y),
+                partial_min(self.end.z, other.end.z),
+            ),
+        );
+
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// Smallest box containing both boxes.
+    pub fn union(self, other: Self) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
+
+        Cuboid::new(
+            Location3d::new(
+                partial_min(self.start.x, other.start.x),
+                partial_min(self.start.y, other.start.y),
+                partial_min(self.start.z, other.start.z),
+            ),
+            Location3d::new(
+                partial_max(self.end.x, other.end.x),
+                partial_max(self.end.y, other.end.y),
+                partial_max(self.end.z, other.end.z),
+            ),
+        )
+    }
+
+    /// Closest location inside the box. The box must not be empty.
+    pub fn clamp(&self, loc: Location3d<T>) -> Location3d<T> {
+        let last = self.end - Location3d::new(one(), one(), one());
+
+        Location3d::new(
+            partial_min(partial_max(loc.x, self.start.x), last.x),
+            partial_min(partial_max(loc.y, self.start.y), last.y),
+            partial_min(partial_max(loc.z, self.start.z), last.z),
+        )
+    }
+
+    pub fn iter(&self) -> CubeIterator<T> {
+        self.start.iter_range(self.end)
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> IntoIterator for Cuboid<T> {
+    type Item = Location3d<T>;
+    type IntoIter = CubeIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Cuboid;
+    use crate::utils::location3d::Location3d;
+
+    #[test]
+    fn iterates_every_cell_once() {
+        let cuboid = Cuboid::new(Location3d::new(1, -1, 0), Location3d::new(3, 2, 2));
+        let cells = cuboid.iter().collect::<Vec<_>>();
+
+        assert_eq!(cells.len() as i32, cuboid.volume());
+        assert!(cells.iter().all(|&cell| cuboid.contains(cell)));
+        assert_eq!(cells.first(), Some(&Location3d::new(1, -1, 0)));
+        assert_eq!(cells.last(), Some(&Location3d::new(2, 1, 1)));
+    }
+
+    #[test]
+    fn empty_cuboid_has_no_cells() {
+        let cuboid = Cuboid::new(Location3d::new(0, 0, 0), Location3d::new(3, 0, 2));
+        assert_eq!(cuboid.iter().count(), 0);
+        assert_eq!(cuboid.volume(), 0);
+    }
+
+    #[test]
+    fn bounding_box_and_intersection() {
+        let bounds = Cuboid::from_points([Location3d::new(0, 5, 1), Location3d::new(2, 1, 1)]).unwrap();
+        assert_eq!(bounds, Cuboid::new(Location3d::new(0, 1, 1), Location3d::new(3, 6, 2)));
+
+        let other = Cuboid::new(Location3d::new(2, 0, 0), Location3d::new(5, 2, 5));
+        assert_eq!(
+            bounds.intersection(&other),
+            Some(Cuboid::new(Location3d::new(2, 1, 1), Location3d::new(3, 2, 2)))
+        );
+        assert_eq!(bounds.clamp(Location3d::new(10, -10, 1)), Location3d::new(2, 1, 1));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::utils::rng::Rng;
+
+/// Cases per target when `AOC_FUZZ_CASES` isn't set, small enough to run with every `cargo test`.
+const DEFAULT_CASES: usize = 300;
+
+/// Time a single case may take before the parser is considered stuck.
+const CASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Endless stream of random mutations of a well-formed input.
+///
+/// Mutations keep most of the structure, so parsers get past their first few tokens before running into trouble.
+pub struct Mutations {
+    seed: Vec<char>,
+    alphabet: Vec<char>,
+    rng: Rng,
+}
+
+impl Mutations {
+    pub fn new(seed: &str, rng_seed: u64) -> Self {
+        let alphabet = seed
+            .chars()
+            .chain("\n -0123456789".chars())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        Self {
+            seed: seed.chars().collect(),
+            alphabet,
+            rng: Rng::new(rng_seed),
+        }
+    }
+
+    fn mutate(&mut self, input: &mut Vec<char>) {
+        let position = self.rng.below(input.len() + 1);
+        let length = 1 + self.rng.below(8);
+        let end = (position + length).min(input.len());
+
+        match self.rng.below(6) {
+            0 if position < input.len() => input[position] = *self.rng.choose(&self.alphabet),
+            1 => input.insert(position, *self.rng.choose(&self.alphabet)),
+            2 => {
+                input.drain(position..end);
+            }
+            3 => {
+                let copy = input[position..end].to_vec();
+                input.splice(position..position, copy);
+            }
+            4 => {
+                let number = *self
+                    .rng
+                    .choose(&["0", "-1", "4294967296", "99999999999999999999999999999999999999999"]);
+                input.splice(position..end, number.chars());
+            }
+            _ => input.truncate(position),
+        }
+    }
+}
+
+impl Iterator for Mutations {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut input = self.seed.clone();
+        for _ in 0..=self.rng.below(4) {
+            self.mutate(&mut input);
+        }
+        Some(input.into_iter().collect())
+    }
+}
+
+/// Feeds mutations of `seed` to `parser`, which returns whether the input parsed.
+///
+/// Fails with the offending input if the parser panics (including arithmetic overflow in debug builds) or takes longer
+/// than a few seconds on a single input. The number of cases can be raised with the `AOC_FUZZ_CASES` env variable.
+pub fn fuzz_parser(seed: &str, parser: impl Fn(&str) -> bool + Send + 'static) {
+    assert!(parser(seed), "the fuzz seed has to be valid input for the parser");
+
+    let cases = env::var("AOC_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES);
+    let inputs = Arc::new(Mutations::new(seed, 0x2023).take(cases).collect::<Vec<_>>());
+
+    let (sender, receiver) = mpsc::channel();
+    let worker = {
+        let inputs = Arc::clone(&inputs);
+        thread::spawn(move || {
+            for (index, input) in inputs.iter().enumerate() {
+                sender.send(index).unwrap();
+                parser(input);
+            }
+        })
+    };
+
+    let mut current = None;
+    loop {
+        match receiver.recv_timeout(CASE_TIMEOUT) {
+            Ok(index) => current = Some(index),
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                panic!("parser got stuck on input {:?}", current.map(|index| &inputs[index]))
+            }
+        }
+    }
+
+    if worker.join().is_err() {
+        panic!("parser panicked on input {:?}", current.map(|index| &inputs[index]));
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{fuzz_parser, Mutations};
+    use crate::utils::{parse_input_by_lines, Parsable};
+
+    #[test]
+    fn mutations_are_deterministic() {
+        let first = Mutations::new("1 2 3\n4 5 6", 7).take(20).collect::<Vec<_>>();
+        let second = Mutations::new("1 2 3\n4 5 6", 7).take(20).collect::<Vec<_>>();
+
+        assert_eq!(first, second);
+        assert!(first.iter().any(|input| input != "1 2 3\n4 5 6"));
+    }
+
+    #[test]
+    fn integer_parsers_survive_fuzzing() {
+        fuzz_parser("1 -2 3\n40 5 -60", |input| {
+            parse_input_by_lines(Vec::<i8>::parse)(input).is_ok()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "parser panicked")]
+    fn reports_panicking_parsers() {
+        fuzz_parser("123", |input| input.parse::<u8>().unwrap() > 0);
+    }
+}
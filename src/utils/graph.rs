@@ -0,0 +1,639 @@
+use std::borrow::Borrow;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use num::Num;
+
+/// Maps names to dense indices, numbered in order of first appearance.
+#[derive(Debug, Clone)]
+pub struct Interner<K> {
+    indices: HashMap<K, usize>,
+    names: Vec<K>,
+}
+
+impl<K> Default for Interner<K> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            names: vec![],
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Interner<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of `name`, assigning the next free one if it wasn't seen yet.
+    pub fn intern<Q>(&mut self, name: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+
+        let index = self.names.len();
+        self.names.push(name.to_owned());
+        self.indices.insert(name.to_owned(), index);
+        index
+    }
+
+    pub fn get<Q>(&self, name: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(name).copied()
+    }
+
+    pub fn name(&self, index: usize) -> &K {
+        &self.names[index]
+    }
+
+    pub fn names(&self) -> &[K] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Adjacency list graph over nodes `0..len`, with a weight of type `W` on every edge.
+///
+/// Undirected graphs store every edge in both directions. Nodes are added on demand by [`Graph::add_edge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<W = ()> {
+    directed: bool,
+    adjacency: Vec<Vec<(usize, W)>>,
+}
+
+impl<W: Clone> Graph<W> {
+    pub fn directed(len: usize) -> Self {
+        Self {
+            directed: true,
+            adjacency: vec![vec![]; len],
+        }
+    }
+
+    pub fn undirected(len: usize) -> Self {
+        Self {
+            directed: false,
+            adjacency: vec![vec![]; len],
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(vec![]);
+        self.adjacency.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        let len = self.len().max(from + 1).max(to + 1);
+        self.adjacency.resize(len, vec![]);
+
+        if !self.directed {
+            self.adjacency[to].push((from, weight.clone()));
+        }
+        self.adjacency[from].push((to, weight));
+    }
+
+    /// Outgoing `(to, weight)` edges of a node.
+    pub fn edges(&self, node: usize) -> &[(usize, W)] {
+        &self.adjacency[node]
+    }
+
+    pub fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency[node].iter().map(|&(to, _)| to)
+    }
+
+    /// Every edge as `(from, to, weight)`. Undirected edges are listed once per direction.
+    pub fn all_edges(&self) -> impl Iterator<Item = (usize, usize, &W)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, weight)| (from, *to, weight)))
+    }
+
+    /// The same graph with every edge pointing the other way.
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self {
+            directed: self.directed,
+            adjacency: vec![vec![]; self.len()],
+        };
+
+        for (from, to, weight) in self.all_edges() {
+            reversed.adjacency[to].push((from, weight.clone()));
+        }
+
+        reversed
+    }
+
+    /// Nodes ordered so every edge points forward, or `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        for (_, to, _) in self.all_edges() {
+            in_degree[to] += 1;
+        }
+
+        let mut queue = (0..self.len()).filter(|&node| in_degree[node] == 0).collect::<VecDeque<_>>();
+        let mut order = vec![];
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for to in self.neighbours(node) {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Components where every node can reach every other one, in reverse topological order.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: vec![None; self.len()],
+            low_link: vec![0; self.len()],
+            on_stack: vec![false; self.len()],
+            stack: vec![],
+            next_index: 0,
+            components: vec![],
+        };
+
+        for node in 0..self.len() {
+            if tarjan.index[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+
+        tarjan.components
+    }
+
+    /// Components connected while ignoring edge direction, each sorted by node index.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let reversed = self.reversed();
+        let mut component = vec![None; self.len()];
+        let mut components = vec![];
+
+        for root in 0..self.len() {
+            if component[root].is_some() {
+                continue;
+            }
+
+            let mut nodes = vec![];
+            let mut queue = vec![root];
+            component[root] = Some(components.len());
+
+            while let Some(node) = queue.pop() {
+                nodes.push(node);
+
+                for next in self.neighbours(node).chain(reversed.neighbours(node)) {
+                    if component[next].is_none() {
+                        component[next] = Some(components.len());
+                        queue.push(next);
+                    }
+                }
+            }
+
+            nodes.sort_unstable();
+            components.push(nodes);
+        }
+
+        components
+    }
+
+    /// Immediate dominator of every node reachable from `root`: the last node every path from `root` must pass.
+    ///
+    /// The root is its own dominator, unreachable nodes have `None`.
+    pub fn dominators(&self, root: usize) -> Vec<Option<usize>> {
+        let mut post_order = vec![];
+        let mut visited = vec![false; self.len()];
+        self.post_order(root, &mut visited, &mut post_order);
+
+        let mut order = vec![usize::MAX; self.len()];
+        for (i, &node) in post_order.iter().enumerate() {
+            order[node] = i;
+        }
+
+        let predecessors = self.reversed();
+        let mut dominators = vec![None; self.len()];
+        dominators[root] = Some(root);
+
+        let intersect = |dominators: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while order[a] < order[b] {
+                    a = dominators[a].unwrap();
+                }
+                while order[b] < order[a] {
+                    b = dominators[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for &node in post_order.iter().rev().skip(1) {
+                let new_dominator = predecessors
+                    .neighbours(node)
+                    .filter(|&predecessor| dominators[predecessor].is_some())
+                    .reduce(|a, b| intersect(&dominators, a, b));
+
+                if new_dominator != dominators[node] {
+                    dominators[node] = new_dominator;
+                    changed = true;
+                }
+            }
+        }
+
+        dominators
+    }
+
+    fn post_order(&self, node: usize, visited: &mut Vec<bool>, order: &mut Vec<usize>) {
+        visited[node] = true;
+        for next in self.neighbours(node) {
+            if !visited[next] {
+                self.post_order(next, visited, order);
+            }
+        }
+        order.push(node);
+    }
+
+    /// Edges of an undirected graph whose removal disconnects it, as `(from, to)` with `from < to`.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.low_links().bridges
+    }
+
+    /// Nodes of an undirected graph whose removal disconnects it, sorted.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        let articulation = self.low_links().articulation;
+        (0..self.len()).filter(|&node| articulation[node]).collect()
+    }
+
+    fn low_links(&self) -> LowLinks<'_, W> {
+        let mut low_links = LowLinks {
+            graph: self,
+            index: vec![None; self.len()],
+            low_link: vec![0; self.len()],
+            next_index: 0,
+            bridges: vec![],
+            articulation: vec![false; self.len()],
+        };
+
+        for node in 0..self.len() {
+            if low_links.index[node].is_none() {
+                let children = low_links.visit(node, None);
+                low_links.articulation[node] = children > 1;
+            }
+        }
+
+        low_links.bridges.sort_unstable();
+        low_links
+    }
+}
+
+/// Global minimum cut of an undirected graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut<W> {
+    /// Total weight of the cut edges.
+    pub weight: W,
+    /// Edges crossing the cut as `(from, to)`, with `from` in `left`.
+    pub edges: Vec<(usize, usize)>,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+impl<W: Num + Copy + Ord> Graph<W> {
+    /// Deterministic global minimum cut of an undirected graph (Stoer–Wagner), `None` if it has less than 2 nodes.
+    pub fn min_cut(&self) -> Option<MinCut<W>> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let mut adjacency = vec![HashMap::<usize, W>::new(); self.len()];
+        for (from, to, &weight) in self.all_edges().filter(|&(from, to, _)| from != to) {
+            let total = adjacency[from].entry(to).or_insert(W::zero());
+            *total = *total + weight;
+        }
+
+        let mut members = (0..self.len()).map(|node| vec![node]).collect::<Vec<_>>();
+        let mut active = (0..self.len()).collect::<Vec<_>>();
+        let mut best: Option<(W, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let (s, t, cut_of_phase) = Self::maximum_adjacency_phase(&adjacency, &active);
+
+            if best.as_ref().is_none_or(|(weight, _)| cut_of_phase < *weight) {
+                best = Some((cut_of_phase, members[t].clone()));
+            }
+
+            // merge t into s
+            let merged = std::mem::take(&mut members[t]);
+            members[s].extend(merged);
+
+            for (next, weight) in std::mem::take(&mut adjacency[t]) {
+                adjacency[next].remove(&t);
+                if next == s {
+                    continue;
+                }
+
+                let total = adjacency[s].entry(next).or_insert(W::zero());
+                *total = *total + weight;
+                let total = adjacency[next].entry(s).or_insert(W::zero());
+                *total = *total + weight;
+            }
+
+            active.retain(|&node| node != t);
+        }
+
+        let (weight, mut left) = best?;
+        left.sort_unstable();
+
+        let mut in_left = vec![false; self.len()];
+        left.iter().for_each(|&node| in_left[node] = true);
+
+        let right = (0..self.len()).filter(|&node| !in_left[node]).collect();
+        let edges = self
+            .all_edges()
+            .filter(|&(from, to, _)| in_left[from] && !in_left[to])
+            .map(|(from, to, _)| (from, to))
+            .collect();
+
+        Some(MinCut {
+            weight,
+            edges,
+            left,
+            right,
+        })
+    }
+
+    /// Adds nodes in order of how strongly they connect to the ones added before.
+    /// Returns the last two nodes and the weight connecting the last one to the rest.
+    fn maximum_adjacency_phase(adjacency: &[HashMap<usize, W>], active: &[usize]) -> (usize, usize, W) {
+        let mut connectivity = vec![W::zero(); adjacency.len()];
+        let mut added = vec![false; adjacency.len()];
+        let mut queue = BinaryHeap::from([(W::zero(), active[0])]);
+        let mut order = vec![];
+
+        while let Some((weight, node)) = queue.pop() {
+            if added[node] || weight != connectivity[node] {
+                continue;
+            }
+
+            added[node] = true;
+            order.push(node);
+
+            for (&next, &edge_weight) in &adjacency[node] {
+                if !added[next] {
+                    connectivity[next] = connectivity[next] + edge_weight;
+                    queue.push((connectivity[next], next));
+                }
+            }
+        }
+
+        // nodes disconnected from the first one never get queued, and are cut off for free
+        if let Some(&disconnected) = active.iter().find(|&&node| !added[node]) {
+            return (order[order.len() - 1], disconnected, W::zero());
+        }
+
+        let t = order[order.len() - 1];
+        (order[order.len() - 2], t, connectivity[t])
+    }
+}
+
+struct Tarjan<'a, W> {
+    graph: &'a Graph<W>,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<W: Clone> Tarjan<'_, W> {
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for next in self.graph.neighbours(node) {
+            match self.index[next] {
+                None => {
+                    self.visit(next);
+                    self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.low_link[node] = self.low_link[node].min(index);
+                }
+                _ => {}
+            }
+        }
+
+        if Some(self.low_link[node]) == self.index[node] {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+struct LowLinks<'a, W> {
+    graph: &'a Graph<W>,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    next_index: usize,
+    bridges: Vec<(usize, usize)>,
+    articulation: Vec<bool>,
+}
+
+impl<W: Clone> LowLinks<'_, W> {
+    /// Returns the number of DFS children of `node`.
+    fn visit(&mut self, node: usize, parent: Option<usize>) -> usize {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+
+        let mut children = 0;
+        let mut skipped_parent = false;
+
+        for next in self.graph.neighbours(node) {
+            // only the tree edge itself is skipped, so parallel edges still count as a cycle
+            if Some(next) == parent && !skipped_parent {
+                skipped_parent = true;
+                continue;
+            }
+
+            match self.index[next] {
+                None => {
+                    children += 1;
+                    self.visit(next, Some(node));
+                    self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+
+                    if Some(self.low_link[next]) > self.index[node] {
+                        self.bridges.push((node.min(next), node.max(next)));
+                    }
+                    if parent.is_some() && Some(self.low_link[next]) >= self.index[node] {
+                        self.articulation[node] = true;
+                    }
+                }
+                Some(index) => {
+                    self.low_link[node] = self.low_link[node].min(index);
+                }
+            }
+        }
+
+        children
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Graph, Interner, MinCut};
+
+    fn directed(edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::directed(0);
+        for &(from, to) in edges {
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    fn undirected(edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::undirected(0);
+        for &(from, to) in edges {
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn interner_assigns_indices_in_order() {
+        let mut interner = Interner::<String>::new();
+
+        assert_eq!(interner.intern("broadcaster"), 0);
+        assert_eq!(interner.intern("a"), 1);
+        assert_eq!(interner.intern("broadcaster"), 0);
+        assert_eq!(interner.get("a"), Some(1));
+        assert_eq!(interner.name(1), "a");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn topological_sort_detects_cycles() {
+        assert_eq!(directed(&[(2, 1), (1, 0), (2, 0)]).topological_sort(), Some(vec![2, 1, 0]));
+        assert_eq!(directed(&[(0, 1), (1, 0)]).topological_sort(), None);
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let graph = directed(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)]);
+        let mut components = graph.strongly_connected_components();
+        components.iter_mut().for_each(|component| component.sort_unstable());
+
+        assert_eq!(components, vec![vec![3, 4], vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        let graph = directed(&[(1, 0), (2, 3), (4, 3)]);
+        assert_eq!(graph.connected_components(), vec![vec![0, 1], vec![2, 3, 4]]);
+    }
+
+    #[test]
+    fn dominators() {
+        let graph = directed(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)]);
+        assert_eq!(graph.dominators(0), vec![Some(0), Some(0), Some(0), Some(0), Some(3), None]);
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        let graph = undirected(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6), (5, 6)]);
+
+        assert_eq!(graph.bridges(), vec![(2, 3)]);
+        assert_eq!(graph.articulation_points(), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn min_cut_finds_lightest_separation() {
+        let mut graph = Graph::undirected(0);
+        for (from, to, weight) in [
+            (0, 1, 2),
+            (0, 4, 3),
+            (1, 2, 3),
+            (1, 4, 2),
+            (1, 5, 2),
+            (2, 3, 4),
+            (2, 6, 2),
+            (3, 6, 2),
+            (3, 7, 2),
+            (4, 5, 3),
+            (5, 6, 1),
+            (6, 7, 3),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+
+        let cut = graph.min_cut().unwrap();
+        let (left, right) = if cut.left.contains(&0) {
+            (cut.left, cut.right)
+        } else {
+            (cut.right, cut.left)
+        };
+
+        assert_eq!(cut.weight, 4);
+        assert_eq!(cut.edges.len(), 2);
+        assert_eq!(left, vec![0, 1, 4, 5]);
+        assert_eq!(right, vec![2, 3, 6, 7]);
+    }
+
+    #[test]
+    fn min_cut_of_disconnected_graph_is_empty() {
+        let mut graph = Graph::undirected(3);
+        graph.add_edge(0, 1, 5u32);
+
+        assert_eq!(
+            graph.min_cut(),
+            Some(MinCut {
+                weight: 0,
+                edges: vec![],
+                left: vec![2],
+                right: vec![0, 1],
+            })
+        );
+    }
+}
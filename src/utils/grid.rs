@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+
+use crate::utils::location::{Access2d, Location};
+
+/// Which cells count as adjacent when walking a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighbourhood {
+    /// Up, down, left and right.
+    Orthogonal,
+    /// Orthogonal and diagonal cells.
+    Moore,
+}
+
+impl Neighbourhood {
+    pub fn of(self, loc: Location<i32>) -> Vec<Location<i32>> {
+        match self {
+            Neighbourhood::Orthogonal => loc.iter_adjacent().into_iter().collect(),
+            Neighbourhood::Moore => loc.neighbours(),
+        }
+    }
+}
+
+fn grid_like<T, U: Clone>(grid: &[Vec<T>], value: U) -> Vec<Vec<U>> {
+    grid.iter().map(|row| vec![value.clone(); row.len()]).collect()
+}
+
+/// Reads one row of characters per line. Returns `None` if there are no rows or they differ in length.
+pub fn parse_grid(input: &str) -> Option<Vec<Vec<char>>> {
+    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = grid.first()?.len();
+
+    (width > 0 && grid.iter().all(|row| row.len() == width)).then_some(grid)
+}
+
+/// Marks every passable cell reachable from any of the seeds. Impassable seeds are ignored.
+pub fn flood_fill<T>(
+    grid: &Vec<Vec<T>>,
+    seeds: impl IntoIterator<Item = Location<i32>>,
+    neighbourhood: Neighbourhood,
+    passable: impl Fn(&T) -> bool,
+) -> Vec<Vec<bool>> {
+    let mut filled = grid_like(grid, false);
+    let mut queue = seeds.into_iter().collect::<Vec<_>>();
+
+    while let Some(current) = queue.pop() {
+        if !grid.get_2d(current).is_some_and(&passable) || filled.get_2d(current) != Some(&false) {
+            continue;
+        }
+
+        filled.set_2d(current, true);
+        queue.extend(neighbourhood.of(current));
+    }
+
+    filled
+}
+
+/// Shortest number of steps from the nearest seed to every passable cell, `None` if unreachable.
+pub fn distances<T>(
+    grid: &Vec<Vec<T>>,
+    seeds: impl IntoIterator<Item = Location<i32>>,
+    neighbourhood: Neighbourhood,
+    passable: impl Fn(&T) -> bool,
+) -> Vec<Vec<Option<usize>>> {
+    distances_within(grid, seeds, usize::MAX, neighbourhood, passable)
+}
+
+/// Same as [`distances`], but stops exploring after `max_distance` steps.
+pub fn distances_within<T>(
+    grid: &Vec<Vec<T>>,
+    seeds: impl IntoIterator<Item = Location<i32>>,
+    max_distance: usize,
+    neighbourhood: Neighbourhood,
+    passable: impl Fn(&T) -> bool,
+) -> Vec<Vec<Option<usize>>> {
+    let mut distances = grid_like(grid, None);
+    let mut queue = seeds.into_iter().map(|seed| (seed, 0)).collect::<VecDeque<_>>();
+
+    while let Some((current, distance)) = queue.pop_front() {
+        if distance > max_distance {
+            continue;
+        }
+        if !grid.get_2d(current).is_some_and(&passable) || distances.get_2d(current) != Some(&None) {
+            continue;
+        }
+
+        distances.set_2d(current, Some(distance));
+
+        for next in neighbourhood.of(current) {
+            queue.push_back((next, distance + 1));
+        }
+    }
+
+    distances
+}
+
+/// Counts cells where a walk of exactly `steps` orthogonal steps from `start` can end.
+///
+/// Walks may step back and forth, so a cell is reachable when its distance is at most `steps` and has the same parity.
+pub fn count_reachable_with_parity<T>(
+    grid: &Vec<Vec<T>>,
+    start: Location<i32>,
+    steps: usize,
+    passable: impl Fn(&T) -> bool,
+) -> usize {
+    distances_within(grid, [start], steps, Neighbourhood::Orthogonal, passable)
+        .iter()
+        .flatten()
+        .flatten()
+        .filter(|&&distance| distance % 2 == steps % 2)
+        .count()
+}
+
+/// Connected regions of passable cells, numbered from 0 in reading order of their first cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    pub labels: Vec<Vec<Option<usize>>>,
+    pub sizes: Vec<usize>,
+}
+
+pub fn label_regions<T>(grid: &Vec<Vec<T>>, neighbourhood: Neighbourhood, passable: impl Fn(&T) -> bool) -> Regions {
+    let mut labels = grid_like(grid, None);
+    let mut sizes = vec![];
+
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            let loc = Location::new(x as i32, y as i32);
+            if !grid.get_2d(loc).is_some_and(&passable) || labels.get_2d(loc) != Some(&None) {
+                continue;
+            }
+
+            let label = sizes.len();
+            let mut size = 0;
+            let mut queue = vec![loc];
+
+            while let Some(current) = queue.pop() {
+                if !grid.get_2d(current).is_some_and(&passable) || labels.get_2d(current) != Some(&None) {
+                    continue;
+                }
+
+                labels.set_2d(current, Some(label));
+                size += 1;
+                queue.extend(neighbourhood.of(current));
+            }
+
+            sizes.push(size);
+        }
+    }
+
+    Regions { labels, sizes }
+}
+
+impl Regions {
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    pub fn label(&self, loc: Location<i32>) -> Option<usize> {
+        self.labels.get_2d(loc).copied().flatten()
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (Location<i32>, usize)> + '_ {
+        self.labels.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter_map(move |(x, label)| label.map(|label| (Location::new(x as i32, y as i32), label)))
+        })
+    }
+
+    /// Number of cell edges on the border of each region.
+    pub fn perimeters(&self) -> Vec<usize> {
+        let mut perimeters = vec![0; self.len()];
+
+        for (loc, label) in self.cells() {
+            perimeters[label] += loc
+                .iter_adjacent()
+                .into_iter()
+                .filter(|&next| self.label(next) != Some(label))
+                .count();
+        }
+
+        perimeters
+    }
+
+    /// Number of straight sides of each region, counting holes. Equal to the number of corners.
+    pub fn sides(&self) -> Vec<usize> {
+        let mut sides = vec![0; self.len()];
+
+        for (loc, label) in self.cells() {
+            let inside = |offset: Location<i32>| self.label(loc + offset) == Some(label);
+
+            for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let horizontal = inside(Location::new(dx, 0));
+                let vertical = inside(Location::new(0, dy));
+                let diagonal = inside(Location::new(dx, dy));
+
+                if (!horizontal && !vertical) || (horizontal && vertical && !diagonal) {
+                    sides[label] += 1;
+                }
+            }
+        }
+
+        sides
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{count_reachable_with_parity, distances, label_regions, parse_grid, Neighbourhood};
+    use crate::utils::location::Location;
+
+    fn parse(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn labels_regions_with_perimeter_and_sides() {
+        let grid = parse("AAB\nA.B\nAAB");
+        let regions = label_regions(&grid, Neighbourhood::Orthogonal, |&c| c == 'A');
+
+        assert_eq!(regions.sizes, vec![5]);
+        assert_eq!(regions.perimeters(), vec![12]);
+        assert_eq!(regions.sides(), vec![8]);
+        assert_eq!(regions.label(Location::new(1, 1)), None);
+    }
+
+    #[test]
+    fn diagonal_regions_depend_on_neighbourhood() {
+        let grid = parse("#.\n.#");
+
+        assert_eq!(label_regions(&grid, Neighbourhood::Orthogonal, |&c| c == '#').len(), 2);
+        assert_eq!(label_regions(&grid, Neighbourhood::Moore, |&c| c == '#').len(), 1);
+    }
+
+    #[test]
+    fn distances_go_around_walls() {
+        let grid = parse("...\n##.\n...");
+        let distances = distances(&grid, [Location::new(0, 0)], Neighbourhood::Orthogonal, |&c| c != '#');
+
+        assert_eq!(distances[2][0], Some(6));
+        assert_eq!(distances[1][0], None);
+    }
+
+    #[test]
+    fn reachable_with_parity() {
+        let grid = parse(".....\n.....\n.....");
+
+        assert_eq!(count_reachable_with_parity(&grid, Location::new(2, 1), 1, |&c| c != '#'), 4);
+        assert_eq!(count_reachable_with_parity(&grid, Location::new(2, 1), 2, |&c| c != '#'), 7);
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        assert_eq!(parse_grid("ab\ncd\n"), Some(parse("ab\ncd")));
+        assert_eq!(parse_grid("ab\nc"), None);
+        assert_eq!(parse_grid(""), None);
+        assert_eq!(parse_grid("\n\n"), None);
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Display;
+
+use num::{BigInt, BigRational, One, Zero};
+
+/// Dense matrix of exact fractions, for solving small linear systems without rounding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: Vec<Vec<BigRational>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The right hand side doesn't have one entry per row.
+    DimensionMismatch,
+    /// The equations contradict each other.
+    Inconsistent,
+    /// There are infinitely many solutions.
+    Underdetermined,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::DimensionMismatch => write!(f, "right hand side doesn't match the matrix dimensions."),
+            SolveError::Inconsistent => write!(f, "the system has no solution."),
+            SolveError::Underdetermined => write!(f, "the system has no unique solution."),
+        }
+    }
+}
+
+impl Matrix {
+    pub fn new(rows: Vec<Vec<BigRational>>) -> Self {
+        assert!(
+            rows.iter().all(|row| row.len() == rows[0].len()),
+            "All matrix rows must have the same length"
+        );
+        Self { rows }
+    }
+
+    pub fn from_integers<T: Clone + Into<BigInt>>(rows: &[Vec<T>]) -> Self {
+        Self::new(
+            rows.iter()
+                .map(|row| row.iter().map(|a| BigRational::from_integer(a.clone().into())).collect())
+                .collect(),
+        )
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    pub fn rows(&self) -> &[Vec<BigRational>] {
+        &self.rows
+    }
+
+    /// Brings the matrix to reduced row echelon form in place.
+    /// Only the first `columns` columns get pivots. Returns the pivot column of every non-zero row.
+    fn reduce(&mut self, columns: usize) -> Vec<usize> {
+        let mut pivots = vec![];
+
+        for column in 0..columns {
+            let row = pivots.len();
+            let Some(pivot) = (row..self.row_count()).find(|&i| !self.rows[i][column].is_zero()) else {
+                continue;
+            };
+
+            self.rows.swap(pivot, row);
+
+            let factor = self.rows[row][column].clone();
+            self.rows[row].iter_mut().for_each(|a| *a /= factor.clone());
+
+            for other in 0..self.row_count() {
+                if other == row || self.rows[other][column].is_zero() {
+                    continue;
+                }
+
+                let factor = self.rows[other][column].clone();
+                for i in column..self.rows[other].len() {
+                    let subtract = self.rows[row][i].clone() * factor.clone();
+                    self.rows[other][i] -= subtract;
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().reduce(self.column_count()).len()
+    }
+
+    /// Determinant of a square matrix, `None` if the matrix isn't square.
+    pub fn determinant(&self) -> Option<BigRational> {
+        if self.row_count() != self.column_count() {
+            return None;
+        }
+
+        // plain elimination to upper triangular form, the determinant is the product of the diagonal
+        let mut determinant = BigRational::one();
+        let mut reduced = self.clone();
+
+        for column in 0..self.column_count() {
+            let Some(pivot) = (column..self.row_count()).find(|&i| !reduced.rows[i][column].is_zero()) else {
+                return Some(BigRational::zero());
+            };
+
+            if pivot != column {
+                reduced.rows.swap(pivot, column);
+                determinant = -determinant;
+            }
+
+            let pivot_value = reduced.rows[column][column].clone();
+            determinant *= pivot_value.clone();
+
+            for other in column + 1..reduced.row_count() {
+                let factor = reduced.rows[other][column].clone() / pivot_value.clone();
+                for i in column..reduced.column_count() {
+                    let subtract = reduced.rows[column][i].clone() * factor.clone();
+                    reduced.rows[other][i] -= subtract;
+                }
+            }
+        }
+
+        Some(determinant)
+    }
+
+    /// Solves `self * x = rhs` exactly.
+    pub fn solve(&self, rhs: &[BigRational]) -> Result<Vec<BigRational>, SolveError> {
+        if rhs.len() != self.row_count() {
+            return Err(SolveError::DimensionMismatch);
+        }
+
+        let columns = self.column_count();
+        let mut augmented = Matrix::new(
+            self.rows
+                .iter()
+                .zip(rhs)
+                .map(|(row, value)| row.iter().chain([value]).cloned().collect())
+                .collect(),
+        );
+
+        let pivots = augmented.reduce(columns);
+
+        let is_inconsistent = augmented.rows[pivots.len()..].iter().any(|row| !row[columns].is_zero());
+        if is_inconsistent {
+            return Err(SolveError::Inconsistent);
+        }
+        if pivots.len() < columns {
+            return Err(SolveError::Underdetermined);
+        }
+
+        Ok(augmented
+            .rows
+            .into_iter()
+            .take(columns)
+            .map(|row| row[columns].clone())
+            .collect())
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Matrix, SolveError};
+    use num::{BigInt, BigRational};
+
+    fn rationals(values: &[i64]) -> Vec<BigRational> {
+        values.iter().map(|&a| BigRational::from_integer(BigInt::from(a))).collect()
+    }
+
+    #[test]
+    fn solves_unique_system() {
+        let matrix = Matrix::from_integers(&[vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]]);
+
+        assert_eq!(matrix.solve(&rationals(&[8, -11, -3])), Ok(rationals(&[2, 3, -1])));
+        assert_eq!(matrix.rank(), 3);
+        assert_eq!(matrix.determinant(), Some(rationals(&[-1])[0].clone()));
+    }
+
+    #[test]
+    fn reports_degenerate_systems() {
+        let matrix = Matrix::from_integers(&[vec![1, 2], vec![2, 4]]);
+
+        assert_eq!(matrix.rank(), 1);
+        assert_eq!(matrix.determinant(), Some(rationals(&[0])[0].clone()));
+        assert_eq!(matrix.solve(&rationals(&[3, 6])), Err(SolveError::Underdetermined));
+        assert_eq!(matrix.solve(&rationals(&[3, 7])), Err(SolveError::Inconsistent));
+        assert_eq!(matrix.solve(&rationals(&[3])), Err(SolveError::DimensionMismatch));
+    }
+}
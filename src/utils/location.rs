@@ -89,7 +89,7 @@ impl<T: Num + Copy + PartialOrd> Iterator for SquareIterator<T> {
     type Item = Location<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next.x < self.end.x {
+        if self.next.x < self.end.x && self.next.y < self.end.y {
             let result = self.next;
             self.next = self.next + Location::new(one(), zero());
 
@@ -181,16 +181,15 @@ impl<T> Access2d<T> for Vec<Vec<T>> {
     }
 
     fn set_2d(&mut self, loc: Location<i32>, element: T) -> Option<()> {
-        self.get_mut(usize::try_from(loc.y).ok()?)
-            .and_then(|row| {
-                row[usize::try_from(loc.x).ok()?] = element;
-                Some(())
-            })
-            .map(|_| ())
+        let cell = self
+            .get_mut(usize::try_from(loc.y).ok()?)?
+            .get_mut(usize::try_from(loc.x).ok()?)?;
+        *cell = element;
+        Some(())
     }
 
     fn iter_2d_keys(&self) -> SquareIterator<usize> {
-        Location::new(0, 0).iter_range(Location::new(self[0].len(), self.len()))
+        Location::new(0, 0).iter_range(Location::new(self.first().map_or(0, Vec::len), self.len()))
     }
 }
 
@@ -203,3 +202,69 @@ pub mod direction {
     pub const UP: Location<i32> = Location::new(0, -1);
     pub const DOWN: Location<i32> = Location::new(0, 1);
 }
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{Access2d, Location};
+
+    fn location() -> impl Strategy<Value = Location<i64>> {
+        (-1_000_000i64..1_000_000, -1_000_000i64..1_000_000).prop_map(|(x, y)| Location::new(x, y))
+    }
+
+    proptest! {
+        #[test]
+        fn rotating_four_times_is_identity(a in location()) {
+            prop_assert_eq!(a.rotate_90_cw().rotate_90_cw().rotate_90_cw().rotate_90_cw(), a);
+            prop_assert_eq!(a.rotate_90_ccw().rotate_90_ccw().rotate_90_ccw().rotate_90_ccw(), a);
+            prop_assert_eq!(a.rotate_90_cw().rotate_90_ccw(), a);
+            prop_assert_eq!(a.rotate_90_cw().rotate_90_cw(), -a);
+        }
+
+        #[test]
+        fn addition_is_invertible(a in location(), b in location()) {
+            prop_assert_eq!(a + b - b, a);
+            prop_assert_eq!(a + b, b + a);
+            prop_assert_eq!(a - b, -(b - a));
+        }
+
+        #[test]
+        fn manhattan_distance_is_a_metric(a in location(), b in location(), c in location()) {
+            prop_assert_eq!(a.manhattan_distance(a), 0);
+            prop_assert_eq!(a.manhattan_distance(b), b.manhattan_distance(a));
+            prop_assert!(a.manhattan_distance(c) <= a.manhattan_distance(b) + b.manhattan_distance(c));
+            prop_assert_eq!(a.rotate_90_cw().manhattan_distance(b.rotate_90_cw()), a.manhattan_distance(b));
+        }
+
+        #[test]
+        fn iter_range_visits_every_location_once(start in location(), width in -5i64..20, height in -5i64..20) {
+            let end = start + Location::new(width, height);
+            let visited = start.iter_range(end).collect::<Vec<_>>();
+
+            prop_assert_eq!(visited.len() as i64, width.max(0) * height.max(0));
+            prop_assert!(visited.iter().all(|loc| (start.x..end.x).contains(&loc.x) && (start.y..end.y).contains(&loc.y)));
+            prop_assert!(visited.windows(2).all(|pair| (pair[0].y, pair[0].x) < (pair[1].y, pair[1].x)));
+        }
+
+        #[test]
+        fn access_2d_matches_indexing(
+            rows in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..8), 0..8),
+            x in -2i32..10,
+            y in -2i32..10,
+            value: u8,
+        ) {
+            let mut grid = rows.clone();
+            let expected = usize::try_from(y).ok().and_then(|y| rows.get(y)).and_then(|row| row.get(usize::try_from(x).ok()?));
+
+            prop_assert_eq!(grid.get_2d(Location::new(x, y)), expected);
+            prop_assert_eq!(grid.set_2d(Location::new(x, y), value).is_some(), expected.is_some());
+            if expected.is_some() {
+                prop_assert_eq!(grid.get_2d(Location::new(x, y)), Some(&value));
+            }
+
+            let keys = grid.iter_2d_keys().count();
+            prop_assert_eq!(keys, rows.first().map_or(0, Vec::len) * rows.len());
+        }
+    }
+}
@@ -48,11 +48,50 @@ impl<T: Num + Copy + Signed> Location3d<T> {
 }
 
 impl<T: Num + Copy + PartialOrd> Location3d<T> {
+    pub fn iter_range(self, end: Location3d<T>) -> CubeIterator<T> {
+        CubeIterator {
+            start: self,
+            next: self,
+            end,
+        }
+    }
+
     pub fn iter_ray(self, direction: Location3d<T>) -> impl Iterator<Item = Location3d<T>> {
         successors(Some(self), move |&current| Some(current + direction))
     }
 }
 
+pub struct CubeIterator<T: Num + Copy + PartialOrd> {
+    start: Location3d<T>,
+    next: Location3d<T>,
+    end: Location3d<T>,
+}
+
+impl<T: Num + Copy + PartialOrd> Iterator for CubeIterator<T> {
+    type Item = Location3d<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start.x >= self.end.x || self.start.y >= self.end.y || self.next.z >= self.end.z {
+            return None;
+        }
+
+        let result = self.next;
+        self.next.x = self.next.x + one();
+
+        if self.next.x >= self.end.x {
+            self.next.x = self.start.x;
+            self.next.y = self.next.y + one();
+        }
+
+        if self.next.y >= self.end.y {
+            self.next.y = self.start.y;
+            self.next.z = self.next.z + one();
+        }
+
+        Some(result)
+    }
+}
+
 impl<T: Num> Zero for Location3d<T> {
     fn zero() -> Self {
         Location3d::new(zero(), zero(), zero())
@@ -123,3 +162,41 @@ pub mod direction3d {
     pub const UP: Location3d<i32> = Location3d::new(0, 0, 1);
     pub const DOWN: Location3d<i32> = Location3d::new(0, 0, -1);
 }
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::Location3d;
+
+    fn location() -> impl Strategy<Value = Location3d<i64>> {
+        (-1_000_000i64..1_000_000, -1_000_000i64..1_000_000, -1_000_000i64..1_000_000)
+            .prop_map(|(x, y, z)| Location3d::new(x, y, z))
+    }
+
+    proptest! {
+        #[test]
+        fn addition_is_invertible(a in location(), b in location()) {
+            prop_assert_eq!(a + b - b, a);
+            prop_assert_eq!(a + b, b + a);
+            prop_assert_eq!(a - b, -(b - a));
+        }
+
+        #[test]
+        fn manhattan_distance_is_a_metric(a in location(), b in location(), c in location()) {
+            prop_assert_eq!(a.manhattan_distance(a), 0);
+            prop_assert_eq!(a.manhattan_distance(b), b.manhattan_distance(a));
+            prop_assert!(a.manhattan_distance(c) <= a.manhattan_distance(b) + b.manhattan_distance(c));
+            prop_assert_eq!(a.manhattan_distance(b), a.to_2d().manhattan_distance(b.to_2d()) + (a.z - b.z).abs());
+        }
+
+        #[test]
+        fn iter_range_visits_every_location_once(start in location(), size in (-3i64..8, -3i64..8, -3i64..8)) {
+            let end = start + Location3d::new(size.0, size.1, size.2);
+            let visited = start.iter_range(end).collect::<Vec<_>>();
+
+            prop_assert_eq!(visited.len() as i64, size.0.max(0) * size.1.max(0) * size.2.max(0));
+            prop_assert!(visited.windows(2).all(|pair| (pair[0].z, pair[0].y, pair[0].x) < (pair[1].z, pair[1].y, pair[1].x)));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+
+use crate::utils::graph::Graph;
+
+/// How many levels of the search tree get split into separate tasks in [`longest_path_parallel`].
+const PARALLEL_DEPTH: usize = 6;
+
+/// Longest simple path search for small weighted graphs, with the visited set kept in a `u64` bitmask.
+struct Solver {
+    edges: Vec<Vec<(usize, usize)>>,
+    /// Heaviest edge leading into every node; a path enters each node at most once.
+    max_entry: Vec<usize>,
+    end: usize,
+}
+
+/// A path from the search root that hasn't been explored further yet.
+#[derive(Debug, Clone, Copy)]
+struct Prefix {
+    node: usize,
+    visited: u64,
+    length: usize,
+    bound: usize,
+}
+
+impl Solver {
+    fn new(graph: &Graph<usize>, end: usize) -> Self {
+        assert!(graph.len() <= 64, "Graph has {} nodes, at most 64 are supported", graph.len());
+
+        let mut max_entry = vec![0; graph.len()];
+        for (_, to, &weight) in graph.all_edges() {
+            max_entry[to] = max_entry[to].max(weight);
+        }
+
+        Self {
+            edges: (0..graph.len()).map(|node| graph.edges(node).to_vec()).collect(),
+            max_entry,
+            end,
+        }
+    }
+
+    fn root(&self, start: usize) -> Prefix {
+        Prefix {
+            node: start,
+            visited: 1 << start,
+            length: 0,
+            bound: self.max_entry.iter().sum::<usize>() - self.max_entry[start],
+        }
+    }
+
+    fn children(&self, prefix: Prefix) -> impl Iterator<Item = Prefix> + '_ {
+        self.edges[prefix.node]
+            .iter()
+            .filter(move |&&(next, _)| prefix.visited & (1 << next) == 0)
+            .map(move |&(next, weight)| Prefix {
+                node: next,
+                visited: prefix.visited | (1 << next),
+                length: prefix.length + weight,
+                bound: prefix.bound - self.max_entry[next],
+            })
+    }
+
+    /// Depth first search, skipping branches that can't beat `best` even if they used every remaining node.
+    fn search(&self, prefix: Prefix, best: &AtomicUsize) -> Option<usize> {
+        if prefix.node == self.end {
+            best.fetch_max(prefix.length, Ordering::Relaxed);
+            return Some(prefix.length);
+        }
+
+        if prefix.length + prefix.bound < best.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        self.children(prefix).filter_map(|child| self.search(child, best)).max()
+    }
+
+    /// Longest remaining distance to the end, cached by position and visited set.
+    fn memoized(&self, node: usize, visited: u64, cache: &mut HashMap<(usize, u64), Option<usize>>) -> Option<usize> {
+        if node == self.end {
+            return Some(0);
+        }
+        if let Some(&result) = cache.get(&(node, visited)) {
+            return result;
+        }
+
+        let result = self.edges[node]
+            .iter()
+            .filter(|&&(next, _)| visited & (1 << next) == 0)
+            .filter_map(|&(next, weight)| Some(self.memoized(next, visited | (1 << next), cache)? + weight))
+            .max();
+
+        cache.insert((node, visited), result);
+        result
+    }
+}
+
+/// Length of the longest path from `start` to `end` that visits no node twice, `None` if `end` is unreachable.
+///
+/// Uses branch and bound search. Supports graphs with at most 64 nodes, like the ones from [`crate::utils::maze`].
+pub fn longest_path(graph: &Graph<usize>, start: usize, end: usize) -> Option<usize> {
+    let solver = Solver::new(graph, end);
+    solver.search(solver.root(start), &AtomicUsize::new(0))
+}
+
+/// Same as [`longest_path`], but splits the first levels of the search across threads.
+pub fn longest_path_parallel(graph: &Graph<usize>, start: usize, end: usize) -> Option<usize> {
+    let solver = Solver::new(graph, end);
+    let mut prefixes = vec![solver.root(start)];
+
+    for _ in 0..PARALLEL_DEPTH {
+        prefixes = prefixes
+            .into_iter()
+            .flat_map(|prefix| -> Vec<Prefix> {
+                if prefix.node == end {
+                    vec![prefix]
+                } else {
+                    solver.children(prefix).collect()
+                }
+            })
+            .collect();
+    }
+
+    let best = AtomicUsize::new(0);
+    prefixes
+        .into_par_iter()
+        .filter_map(|prefix| solver.search(prefix, &best))
+        .max()
+}
+
+/// Same as [`longest_path`], but caches results by `(node, visited set)` instead of pruning.
+///
+/// Fast when few visited sets are possible (e.g. mostly directed graphs), memory hungry otherwise.
+pub fn longest_path_memoized(graph: &Graph<usize>, start: usize, end: usize) -> Option<usize> {
+    Solver::new(graph, end).memoized(start, 1 << start, &mut HashMap::new())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{longest_path, longest_path_memoized, longest_path_parallel};
+    use crate::utils::graph::Graph;
+
+    fn graph() -> Graph<usize> {
+        let mut graph = Graph::undirected(0);
+        for (from, to, weight) in [(0, 1, 2), (1, 2, 3), (0, 2, 10), (2, 3, 1), (1, 3, 20), (3, 4, 1), (5, 6, 1)] {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn finds_longest_simple_path() {
+        for solve in [longest_path, longest_path_parallel, longest_path_memoized] {
+            assert_eq!(solve(&graph(), 0, 4), Some(34));
+            assert_eq!(solve(&graph(), 0, 0), Some(0));
+            assert_eq!(solve(&graph(), 0, 6), None);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use num::{CheckedAdd, CheckedMul, CheckedSub, Integer, Signed};
+
+/// Signed integers with checked arithmetic, e.g. `i64` or `i128`.
+pub trait CheckedInteger: Integer + Signed + Copy + CheckedAdd + CheckedSub + CheckedMul {}
+
+impl<T: Integer + Signed + Copy + CheckedAdd + CheckedSub + CheckedMul> CheckedInteger for T {}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`, with `g` non-negative.
+pub fn extended_gcd<T: CheckedInteger>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Inverse of `a` modulo `modulus` in `0..modulus`, `None` if they aren't coprime.
+pub fn mod_inverse<T: CheckedInteger>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.mod_floor(&modulus), modulus);
+    g.is_one().then(|| x.mod_floor(&modulus))
+}
+
+/// Solves `x ≡ remainder (mod modulus)` for every pair, moduli don't have to be coprime.
+///
+/// Returns the smallest non-negative solution and the period it repeats with (lcm of the moduli).
+/// `None` if the congruences contradict each other, a modulus isn't positive, or the result overflows.
+pub fn crt<T: CheckedInteger>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences.iter().try_fold(
+        (T::zero(), T::one()),
+        |(remainder, modulus), &(next_remainder, next_modulus)| {
+            if !next_modulus.is_positive() {
+                return None;
+            }
+
+            let (g, p, _) = extended_gcd(modulus, next_modulus);
+            let difference = next_remainder.checked_sub(&remainder)?;
+            if !(difference % g).is_zero() {
+                return None;
+            }
+
+            // remainder + modulus * k solves both, for k ≡ difference / g * p (mod next_modulus / g)
+            let step = next_modulus / g;
+            let k = (difference / g)
+                .mod_floor(&step)
+                .checked_mul(&p.mod_floor(&step))?
+                .mod_floor(&step);
+
+            let lcm = modulus.checked_mul(&step)?;
+            let solution = modulus.checked_mul(&k)?.checked_add(&remainder)?.mod_floor(&lcm);
+
+            Some((solution, lcm))
+        },
+    )
+}
+
+/// First time at which every cycle is active, where a cycle `(offset, period)` is active at `offset + k * period`.
+///
+/// `None` if the cycles never line up or the result overflows.
+pub fn first_common_time<T: CheckedInteger>(cycles: &[(T, T)]) -> Option<T> {
+    let (solution, period) = crt(cycles)?;
+    let earliest = cycles.iter().map(|&(offset, _)| offset).max()?;
+
+    if solution >= earliest {
+        return Some(solution);
+    }
+
+    let periods = earliest.checked_sub(&solution)?.div_ceil(&period);
+    solution.checked_add(&periods.checked_mul(&period)?)
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{crt, extended_gcd, first_common_time, mod_inverse};
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        for (a, b) in [(240i64, 46), (-240, 46), (17, 0), (0, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g, num::integer::gcd(a, b));
+        }
+    }
+
+    #[test]
+    fn modular_inverse() {
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+    }
+
+    #[test]
+    fn crt_with_non_coprime_moduli() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3i64, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn crt_detects_overflow() {
+        assert_eq!(crt(&[(0i64, 4_000_000_007), (1, 4_000_000_009), (2, 4_000_000_021)]), None);
+        assert!(crt(&[(0i128, 4_000_000_007), (1, 4_000_000_009), (2, 4_000_000_021)]).is_some());
+    }
+
+    #[test]
+    fn common_time_respects_offsets() {
+        assert_eq!(first_common_time(&[(2i64, 2), (3, 3)]), Some(6));
+        assert_eq!(first_common_time(&[(10i64, 4), (0, 2)]), Some(10));
+        assert_eq!(first_common_time(&[(1i64, 2), (0, 4)]), None);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::utils::graph::Graph;
+use crate::utils::location::{Access2d, Location};
+
+/// A maze compressed to its junctions, with corridors between them turned into weighted edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JunctionGraph {
+    /// Grid location of every node.
+    pub nodes: Vec<Location<i32>>,
+    /// Directed corridors between the nodes, weighted by their length in steps.
+    pub graph: Graph<usize>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl JunctionGraph {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node_at(&self, loc: Location<i32>) -> Option<usize> {
+        self.nodes.iter().position(|&node| node == loc)
+    }
+}
+
+/// Compresses a maze into a graph of its junctions (cells with 3+ passable neighbours) plus `start` and `end`.
+///
+/// `one_way` returns the only direction a tile may be left in (e.g. slopes like `>`), or `None` for free tiles.
+/// Corridors that run into a dead end or against a one-way tile produce no edge.
+pub fn compress<T>(
+    grid: &Vec<Vec<T>>,
+    start: Location<i32>,
+    end: Location<i32>,
+    passable: impl Fn(&T) -> bool,
+    one_way: impl Fn(&T) -> Option<Location<i32>>,
+) -> JunctionGraph {
+    let is_passable = |loc: Location<i32>| grid.get_2d(loc).is_some_and(&passable);
+    let can_step = |from: Location<i32>, to: Location<i32>| {
+        is_passable(to)
+            && grid
+                .get_2d(from)
+                .and_then(&one_way)
+                .is_none_or(|direction| from + direction == to)
+    };
+
+    let mut nodes = vec![start, end];
+    nodes.extend(grid.iter_2d_keys().map(|loc| loc.map(|a| a as i32)).filter(|&loc| {
+        is_passable(loc)
+            && loc != start
+            && loc != end
+            && loc.iter_adjacent().into_iter().filter(|&next| is_passable(next)).count() > 2
+    }));
+
+    let indices = nodes.iter().enumerate().map(|(i, &loc)| (loc, i)).collect::<HashMap<_, _>>();
+    let mut graph = Graph::directed(nodes.len());
+
+    for (from, &junction) in nodes.iter().enumerate() {
+        for first in junction.iter_adjacent() {
+            if !can_step(junction, first) {
+                continue;
+            }
+
+            let mut previous = junction;
+            let mut current = first;
+            let mut steps = 1;
+
+            loop {
+                if let Some(&to) = indices.get(&current) {
+                    graph.add_edge(from, to, steps);
+                    break;
+                }
+
+                let Some(next) = current
+                    .iter_adjacent()
+                    .into_iter()
+                    .find(|&next| next != previous && is_passable(next))
+                else {
+                    break;
+                };
+                if !can_step(current, next) {
+                    break;
+                }
+
+                previous = current;
+                current = next;
+                steps += 1;
+            }
+        }
+    }
+
+    JunctionGraph {
+        nodes,
+        graph,
+        start: 0,
+        end: 1,
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::compress;
+    use crate::utils::location::{direction, Location};
+
+    fn parse(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn compresses_corridors_into_weighted_edges() {
+        let grid = parse("#.###\n#...#\n#.#.#\n#...#\n###.#");
+        let maze = compress(&grid, Location::new(1, 0), Location::new(3, 4), |&c| c != '#', |_| None);
+
+        let junction = maze.node_at(Location::new(1, 1)).unwrap();
+        assert_eq!(maze.len(), 4);
+        assert_eq!(maze.graph.edges(maze.start), [(junction, 1)]);
+        assert_eq!(maze.graph.edges(maze.end), [(maze.node_at(Location::new(3, 3)).unwrap(), 1)]);
+        assert_eq!(maze.graph.edges(junction).iter().filter(|&&(_, steps)| steps == 4).count(), 2);
+    }
+
+    #[test]
+    fn one_way_tiles_only_allow_their_direction() {
+        let grid = parse(".>..");
+        let one_way = |&c: &char| (c == '>').then_some(direction::RIGHT);
+
+        let forward = compress(&grid, Location::new(0, 0), Location::new(3, 0), |_| true, one_way);
+        assert_eq!(forward.graph.edges(forward.start), [(forward.end, 3)]);
+        assert_eq!(forward.graph.edges(forward.end), []);
+
+        let backward = compress(&grid, Location::new(3, 0), Location::new(0, 0), |_| true, one_way);
+        assert_eq!(backward.graph.edges(backward.start), []);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cache for recursive functions, turning plain recursion into top down dynamic programming.
+///
+/// The function gets a `recurse` callback instead of calling itself, every call through it is cached, e.g.
+/// `Memo::new().call(90, &|recurse, n| if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) })`.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            capacity: None,
+        }
+    }
+
+    /// Cache holding at most `capacity` results, it's emptied whenever it fills up.
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            cache: HashMap::with_capacity(capacity),
+            capacity: Some(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity.is_some_and(|capacity| self.cache.len() >= capacity) {
+            self.cache.clear();
+        }
+        self.cache.insert(key, value);
+    }
+
+    /// Evaluates `f(key)`, where `f` recurses through its first argument.
+    pub fn call<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        self.call_by(key, &K::clone, f)
+    }
+
+    /// Like [`Memo::call`], but for inputs that can't be cached directly, like borrowed slices.
+    ///
+    /// Results are cached under `key(&input)`, e.g. the length of a slice when the recursion only ever takes suffixes.
+    pub fn call_by<I, F>(&mut self, input: I, key: &impl Fn(&I) -> K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(I) -> V, I) -> V,
+    {
+        let cache_key = key(&input);
+        if let Some(value) = self.cache.get(&cache_key) {
+            return value.clone();
+        }
+
+        let value = f(&mut |next| self.call_by(next, key, f), input);
+        self.insert(cache_key, value.clone());
+        value
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Memo;
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        let fibonacci = memo.call(90u64, &|recurse, n| if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) });
+
+        assert_eq!(fibonacci, 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+    }
+
+    #[test]
+    fn caches_slice_suffixes_by_length() {
+        let values = [1u64, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+        // number of ways to split the slice into non-empty parts with an odd sum each
+        let mut memo = Memo::new();
+        let splits = memo.call_by(&values[..], &|suffix| suffix.len(), &|recurse, suffix| {
+            if suffix.is_empty() {
+                return 1;
+            }
+            (1..=suffix.len())
+                .filter(|&length| suffix[..length].iter().sum::<u64>() % 2 == 1)
+                .map(|length| recurse(&suffix[length..]))
+                .sum()
+        });
+
+        assert_eq!(splits, 29);
+        assert_eq!(memo.len(), 11);
+    }
+
+    #[test]
+    fn bounded_cache_stays_small() {
+        let mut memo = Memo::bounded(4);
+        let result = memo.call(20u64, &|recurse, n| if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) });
+
+        assert_eq!(result, 6765);
+        assert!(memo.len() <= 4);
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt::{Debug, Display};
 use std::str::FromStr;
 
 use nom::bytes::complete::is_a;
@@ -8,8 +9,21 @@ use nom::multi::separated_list1;
 use nom::sequence::{pair, terminated};
 use nom::{IResult, Parser};
 
+pub mod cuboid;
+pub mod fuzz;
+pub mod graph;
+pub mod grid;
+pub mod linalg;
 pub mod location;
 pub mod location3d;
+pub mod longest_path;
+pub mod math;
+pub mod maze;
+pub mod memo;
+pub mod poly;
+pub mod rect;
+pub mod rng;
+pub mod viz;
 
 pub fn parse_input_by_lines<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&str, Vec<O>, E>
 where
@@ -33,6 +47,22 @@ pub trait Parsable<'a> {
         Self: Sized;
 }
 
+/// Asserts that `value` displays as puzzle input which parses back to the same value, without leftovers.
+pub fn assert_round_trip<T>(value: &T)
+where
+    T: for<'a> Parsable<'a> + Display + Debug + PartialEq,
+{
+    let displayed = value.to_string();
+
+    match T::parse(&displayed) {
+        Ok((rest, parsed)) => {
+            assert_eq!(&parsed, value, "{displayed:?} parsed into a different value");
+            assert!(rest.is_empty(), "{displayed:?} was not parsed completely, {rest:?} is left");
+        }
+        Err(err) => panic!("{displayed:?} could not be parsed: {err}"),
+    }
+}
+
 macro_rules! impl_parsable_uint {
     (for $($t:ty),+) => {
         $(
@@ -66,3 +96,55 @@ impl<'a, T: Parsable<'a>> Parsable<'a> for Vec<T> {
         separated_list1(space1, T::parse)(input)
     }
 }
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::Parsable;
+
+    macro_rules! round_trip_tests {
+        ($($name:ident: $t:ty),+ $(,)?) => {
+            $(
+                proptest! {
+                    #[test]
+                    fn $name(value: $t, suffix in "[ ,a-z]{0,3}") {
+                        let input = format!("{value}{suffix}");
+                        prop_assert_eq!(<$t>::parse(&input), Ok((suffix.as_str(), value)));
+                    }
+                }
+            )+
+
+            #[test]
+            fn integer_bounds_round_trip() {
+                $(
+                    for value in [<$t>::MIN, <$t>::MAX] {
+                        assert_eq!(<$t>::parse(&value.to_string()), Ok(("", value)));
+                    }
+                )+
+            }
+        };
+    }
+
+    round_trip_tests!(
+        parse_u8: u8,
+        parse_u16: u16,
+        parse_u32: u32,
+        parse_u64: u64,
+        parse_u128: u128,
+        parse_usize: usize,
+        parse_i8: i8,
+        parse_i16: i16,
+        parse_i32: i32,
+        parse_i64: i64,
+        parse_i128: i128,
+        parse_isize: isize,
+    );
+
+    #[test]
+    fn parse_rejects_overflow() {
+        assert!(u8::parse("256").is_err());
+        assert!(i8::parse("-129").is_err());
+        assert!(u32::parse("-1").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use num::rational::Ratio;
+use num::{Integer, Num, Zero};
+
+/// Finite difference table of a sequence: row `k` holds the `k`-th differences.
+///
+/// Stops after the first all zero row, or when the rows run out.
+pub fn differences<T: Num + Copy>(values: &[T]) -> Vec<Vec<T>> {
+    let mut table = vec![values.to_vec()];
+
+    loop {
+        let row = table.last().unwrap();
+        if row.is_empty() || row.iter().all(Zero::is_zero) {
+            break;
+        }
+
+        let next = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        table.push(next);
+    }
+
+    table
+}
+
+/// Polynomial in Newton's forward difference form, fitted to samples at consecutive integers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewtonPolynomial<T> {
+    /// Position of the first sample.
+    origin: T,
+    /// First entry of every row of the difference table, without trailing zeroes.
+    coefficients: Vec<T>,
+}
+
+impl<T: Num + Copy> NewtonPolynomial<T> {
+    /// Fits the lowest degree polynomial through samples taken at `0, 1, 2, ...`.
+    pub fn fit(values: &[T]) -> Self {
+        Self::fit_from(T::zero(), values)
+    }
+
+    /// Fits the lowest degree polynomial through samples taken at `origin, origin + 1, ...`.
+    pub fn fit_from(origin: T, values: &[T]) -> Self {
+        let mut coefficients = differences(values)
+            .into_iter()
+            .filter_map(|row| row.first().copied())
+            .collect::<Vec<_>>();
+
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+
+        Self { origin, coefficients }
+    }
+
+    /// Degree of the polynomial, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Value at any position, including ones before the first sample.
+    ///
+    /// Exact for integers, since `x choose k` is an integer even for negative `x`.
+    pub fn eval(&self, x: T) -> T {
+        let x = x - self.origin;
+        let mut result = T::zero();
+        let mut binomial = T::one();
+        let mut k = T::zero();
+
+        for &coefficient in &self.coefficients {
+            result = result + coefficient * binomial;
+
+            binomial = binomial * (x - k);
+            k = k + T::one();
+            binomial = binomial / k;
+        }
+
+        result
+    }
+}
+
+/// Finds the shortest prefix after which the sequence is polynomial.
+///
+/// The fit has to be confirmed by at least `confirmations` samples beyond the ones needed to determine it.
+/// Returns the prefix length and the polynomial, which is indexed like the original sequence.
+pub fn polynomial_tail<T: Num + Copy>(values: &[T], confirmations: usize) -> Option<(usize, NewtonPolynomial<T>)> {
+    let mut origin = T::zero();
+
+    for prefix in 0..values.len() {
+        let table = differences(&values[prefix..]);
+        let last = table.last().unwrap();
+
+        if last.len() >= confirmations.max(1) && last.iter().all(Zero::is_zero) {
+            return Some((prefix, NewtonPolynomial::fit_from(origin, &values[prefix..])));
+        }
+
+        origin = origin + T::one();
+    }
+
+    None
+}
+
+/// Value at `x` of the polynomial through the given `(x, y)` points (Lagrange form), as an exact fraction.
+pub fn lagrange<T: Integer + Clone>(points: &[(T, T)], x: T) -> Ratio<T> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (xi, yi))| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Ratio::from(yi.clone()), |term, (_, (xj, _))| {
+                    term * Ratio::new(x.clone() - xj.clone(), xi.clone() - xj.clone())
+                })
+        })
+        .fold(Ratio::zero(), |sum, term| sum + term)
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{lagrange, polynomial_tail, NewtonPolynomial};
+    use num::rational::Ratio;
+
+    #[test]
+    fn extrapolates_both_directions() {
+        let polynomial = NewtonPolynomial::fit(&[10, 13, 16, 21, 30, 45]);
+
+        assert_eq!(polynomial.degree(), Some(3));
+        assert_eq!(polynomial.eval(6), 68);
+        assert_eq!(polynomial.eval(-1), 5);
+        assert_eq!(polynomial.eval(-4), -42);
+    }
+
+    #[test]
+    fn finds_polynomial_after_prefix() {
+        let values = [7, -3, 0, 1, 4, 9, 16, 25];
+        let (prefix, polynomial) = polynomial_tail(&values, 2).unwrap();
+
+        assert_eq!(prefix, 2);
+        assert_eq!(polynomial.eval(10), 64);
+        assert_eq!(polynomial_tail(&[1, 2, 4, 8, 16], 1), None);
+    }
+
+    #[test]
+    fn lagrange_is_exact() {
+        assert_eq!(lagrange(&[(0, 0), (2, 4), (3, 9)], 5), Ratio::from(25));
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Ratio::new(1, 2));
+    }
+}
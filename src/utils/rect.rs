@@ -0,0 +1,201 @@
+use num::{one, Num};
+
+use crate::utils::location::{Location, SquareIterator};
+
+/// Axis-aligned rectangle spanning `start` (inclusive) to `end` (exclusive), same as [`Location::iter_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T: Num> {
+    pub start: Location<T>,
+    pub end: Location<T>,
+}
+
+pub(crate) fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+pub(crate) fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T: Num> Rect<T> {
+    pub const fn new(start: Location<T>, end: Location<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Rect<T> {
+    /// Smallest rectangle containing every point, or `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Location<T>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|point| Rect::new(point, point + Location::new(one(), one())))
+            .reduce(Rect::union)
+    }
+
+    pub fn width(&self) -> T {
+        if self.end.x > self.start.x {
+            self.end.x - self.start.x
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn height(&self) -> T {
+        if self.end.y > self.start.y {
+            self.end.y - self.start.y
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn size(&self) -> Location<T> {
+        Location::new(self.width(), self.height())
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.x >= self.end.x || self.start.y >= self.end.y
+    }
+
+    pub fn contains(&self, loc: Location<T>) -> bool {
+        self.start.x <= loc.x && loc.x < self.end.x && self.start.y <= loc.y && loc.y < self.end.y
+    }
+
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (self.start.x <= other.start.x
+                && other.end.x <= self.end.x
+                && self.start.y <= other.start.y
+                && other.end.y <= self.end.y)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Overlapping part of both rectangles, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Rect::new(
+            Location::new(
+                partial_max(self.start.x, other.start.x),
+                partial_max(self.start.y, other.start.y),
+            ),
+            Location::new(partial_min(self.end.x, other.end.x), partial_min(self.end.y, other.end.y)),
+        );
+
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// Smallest rectangle containing both rectangles.
+    pub fn union(self, other: Self) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
+
+        Rect::new(
+            Location::new(
+                partial_min(self.start.x, other.start.x),
+                partial_min(self.start.y, other.start.y),
+            ),
+            Location::new(partial_max(self.end.x, other.end.x), partial_max(self.end.y, other.end.y)),
+        )
+    }
+
+    /// Closest location inside the rectangle. The rectangle must not be empty.
+    pub fn clamp(&self, loc: Location<T>) -> Location<T> {
+        let last = self.end - Location::new(one(), one());
+
+        Location::new(
+            partial_min(partial_max(loc.x, self.start.x), last.x),
+            partial_min(partial_max(loc.y, self.start.y), last.y),
+        )
+    }
+
+    pub fn iter(&self) -> SquareIterator<T> {
+        self.start.iter_range(self.end)
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> IntoIterator for Rect<T> {
+    type Item = Location<T>;
+    type IntoIter = SquareIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Rect;
+    use crate::utils::location::Location;
+
+    #[test]
+    fn bounding_box_of_points() {
+        let bounds = Rect::from_points([Location::new(2, 5), Location::new(-1, 3), Location::new(0, 4)]).unwrap();
+        assert_eq!(bounds, Rect::new(Location::new(-1, 3), Location::new(3, 6)));
+        assert_eq!(bounds.size(), Location::new(4, 3));
+        assert_eq!(Rect::<i32>::from_points([]), None);
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Rect::new(Location::new(0, 0), Location::new(4, 3));
+        let b = Rect::new(Location::new(2, 1), Location::new(6, 5));
+        let apart = Rect::new(Location::new(4, 0), Location::new(5, 3));
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(Location::new(2, 1), Location::new(4, 3))));
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&apart), None);
+        assert!(!a.intersects(&apart));
+
+        assert_eq!(a.union(b), Rect::new(Location::new(0, 0), Location::new(6, 5)));
+        assert_eq!(a.union(Rect::new(Location::new(9, 9), Location::new(9, 9))), a);
+    }
+
+    #[test]
+    fn clamp_and_contains() {
+        let rect = Rect::new(Location::new(1, 1), Location::new(4, 3));
+        assert_eq!(rect.clamp(Location::new(-5, 2)), Location::new(1, 2));
+        assert_eq!(rect.clamp(Location::new(10, 10)), Location::new(3, 2));
+        assert_eq!(rect.clamp(Location::new(2, 2)), Location::new(2, 2));
+
+        assert!(rect.contains_rect(&Rect::new(Location::new(1, 2), Location::new(4, 3))));
+        assert!(!rect.contains_rect(&Rect::new(Location::new(0, 2), Location::new(2, 3))));
+        assert!(rect.contains_rect(&Rect::new(Location::new(9, 9), Location::new(0, 0))));
+    }
+
+    #[test]
+    fn iterates_every_cell_once() {
+        let rect = Rect::new(Location::new(1, -1), Location::new(3, 2));
+        let cells = rect.iter().collect::<Vec<_>>();
+
+        assert_eq!(cells.len() as i32, rect.area());
+        assert!(cells.iter().all(|&cell| rect.contains(cell)));
+        assert_eq!(cells.first(), Some(&Location::new(1, -1)));
+        assert_eq!(cells.last(), Some(&Location::new(2, 1)));
+    }
+
+    #[test]
+    fn empty_unsigned_rect_has_no_size() {
+        let rect = Rect::<u32>::new(Location::new(5, 5), Location::new(2, 7));
+        assert!(rect.is_empty());
+        assert_eq!(rect.size(), Location::new(0, 2));
+        assert_eq!(rect.area(), 0);
+        assert_eq!(rect.iter().count(), 0);
+    }
+}
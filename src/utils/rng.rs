@@ -0,0 +1,68 @@
+use std::ops::Range;
+
+/// Deterministic xorshift generator, for fuzzing and generated inputs where reproducibility beats quality.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero, and close seeds should still give unrelated streams
+        Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform-ish number in `0..bound`, `bound` must be positive.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Uniform-ish number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        let width = range.end.abs_diff(range.start);
+        range.start.wrapping_add_unsigned(self.next_u64() % width)
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::Rng;
+
+    proptest! {
+        #[test]
+        fn range_stays_in_bounds(seed: u64, start in -1000i64..1000, width in 1i64..1000) {
+            let mut rng = Rng::new(seed);
+            for _ in 0..100 {
+                prop_assert!((start..start + width).contains(&rng.range(start..start + width)));
+            }
+        }
+    }
+
+    #[test]
+    fn zero_seed_is_not_stuck() {
+        let mut rng = Rng::new(0);
+        assert_ne!(rng.next_u64(), rng.next_u64());
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::{Display, Formatter};
+
+use crate::utils::graph::Graph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Shape {
+    #[default]
+    Ellipse,
+    Box,
+    Circle,
+    DoubleCircle,
+    Diamond,
+    Triangle,
+    InvTriangle,
+    Point,
+}
+
+impl Shape {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Shape::Ellipse => "ellipse",
+            Shape::Box => "box",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Diamond => "diamond",
+            Shape::Triangle => "triangle",
+            Shape::InvTriangle => "invtriangle",
+            Shape::Point => "point",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    label: String,
+    shape: Shape,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+}
+
+/// Graphviz document, nodes are numbered like in [`Graph`]. Its [`Display`] impl produces the `.dot` source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Dot {
+    pub fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Every node and edge of `graph`, with nodes labeled by their index and edges without labels.
+    pub fn from_graph<W: Clone>(name: &str, graph: &Graph<W>) -> Self {
+        Self::from_graph_with(name, graph, |_| None)
+    }
+
+    /// Like [`Dot::from_graph`], with edges labeled by their weights.
+    pub fn from_weighted_graph<W: Clone + Display>(name: &str, graph: &Graph<W>) -> Self {
+        Self::from_graph_with(name, graph, |weight| Some(weight.to_string()))
+    }
+
+    fn from_graph_with<W: Clone>(name: &str, graph: &Graph<W>, label: impl Fn(&W) -> Option<String>) -> Self {
+        let mut dot = Self::new(name, graph.is_directed());
+
+        for node in 0..graph.len() {
+            dot.node(node, &node.to_string(), Shape::default());
+        }
+
+        // undirected graphs store every edge twice
+        for (from, to, weight) in graph.all_edges() {
+            if graph.is_directed() || from <= to {
+                dot.edge(from, to, label(weight).as_deref());
+            }
+        }
+
+        dot
+    }
+
+    /// Sets the label and shape of a node, adding it and every node before it if needed.
+    pub fn node(&mut self, node: usize, label: &str, shape: Shape) -> &mut Self {
+        if node >= self.nodes.len() {
+            let len = self.nodes.len();
+            self.nodes.extend((len..=node).map(|node| Node {
+                label: node.to_string(),
+                shape: Shape::default(),
+            }));
+        }
+
+        self.nodes[node] = Node {
+            label: label.to_string(),
+            shape,
+        };
+        self
+    }
+
+    pub fn edge(&mut self, from: usize, to: usize, label: Option<&str>) -> &mut Self {
+        self.edges.push(Edge {
+            from,
+            to,
+            label: label.map(String::from),
+        });
+        self
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (keyword, connector) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+
+        writeln!(f, "{keyword} \"{}\" {{", escape(&self.name))?;
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            writeln!(
+                f,
+                "    n{index} [label=\"{}\", shape={}];",
+                escape(&node.label),
+                node.shape.as_str()
+            )?;
+        }
+
+        for edge in &self.edges {
+            write!(f, "    n{} {connector} n{}", edge.from, edge.to)?;
+            match &edge.label {
+                Some(label) => writeln!(f, " [label=\"{}\"];", escape(label))?,
+                None => writeln!(f, ";")?,
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Dot, Shape};
+    use crate::utils::graph::Graph;
+
+    #[test]
+    fn writes_undirected_edges_once() {
+        let mut graph = Graph::undirected(0);
+        graph.add_edge(0, 1, 5);
+        graph.add_edge(1, 2, 7);
+
+        let mut dot = Dot::from_weighted_graph("test", &graph);
+        dot.node(2, "end \"x\"", Shape::Box);
+
+        assert_eq!(
+            dot.to_string(),
+            concat!(
+                "graph \"test\" {\n",
+                "    n0 [label=\"0\", shape=ellipse];\n",
+                "    n1 [label=\"1\", shape=ellipse];\n",
+                "    n2 [label=\"end \\\"x\\\"\", shape=box];\n",
+                "    n0 -- n1 [label=\"5\"];\n",
+                "    n1 -- n2 [label=\"7\"];\n",
+                "}\n",
+            )
+        );
+    }
+}
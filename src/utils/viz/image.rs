@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::utils::location::{Access2d, Location};
+use crate::utils::viz;
+use crate::Day;
+
+pub type Rgb = [u8; 3];
+
+pub mod colour {
+    use super::Rgb;
+
+    pub const BLACK: Rgb = [0, 0, 0];
+    pub const WHITE: Rgb = [255, 255, 255];
+    pub const GREY: Rgb = [96, 96, 96];
+    pub const RED: Rgb = [220, 50, 47];
+    pub const GREEN: Rgb = [133, 153, 0];
+    pub const YELLOW: Rgb = [250, 200, 40];
+    pub const BLUE: Rgb = [38, 139, 210];
+    pub const MAGENTA: Rgb = [211, 54, 130];
+    pub const CYAN: Rgb = [42, 161, 152];
+
+    /// Colours to cycle through when every item needs a different one.
+    pub const PALETTE: [Rgb; 6] = [RED, GREEN, YELLOW, BLUE, MAGENTA, CYAN];
+}
+
+/// RGB raster image, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Draws every cell of the grid as a `scale` x `scale` square.
+    pub fn from_grid<T>(grid: &Vec<Vec<T>>, scale: usize, colour: impl Fn(&T) -> Rgb) -> Self {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut image = Self::new(width * scale, grid.len() * scale, colour::BLACK);
+
+        for loc in grid.iter_2d_keys() {
+            if let Some(cell) = grid.get_2d(loc.map(|a| a as i32)) {
+                image.fill(loc * scale, Location::new(scale, scale), colour(cell));
+            }
+        }
+
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, loc: Location<usize>) -> Option<Rgb> {
+        (loc.x < self.width && loc.y < self.height).then(|| self.pixels[loc.y * self.width + loc.x])
+    }
+
+    /// Sets a pixel, pixels outside of the image are ignored.
+    pub fn set(&mut self, loc: Location<usize>, colour: Rgb) {
+        if loc.x < self.width && loc.y < self.height {
+            self.pixels[loc.y * self.width + loc.x] = colour;
+        }
+    }
+
+    /// Fills the rectangle starting at `start` with the given `size`, clipped to the image.
+    pub fn fill(&mut self, start: Location<usize>, size: Location<usize>, colour: Rgb) {
+        for loc in start.iter_range(start + size) {
+            self.set(loc, colour);
+        }
+    }
+
+    /// Binary PPM (`P6`), readable by most image viewers and trivial to convert.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_png(&mut bytes).expect("writing to memory can't fail");
+        bytes
+    }
+
+    fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        Ok(())
+    }
+
+    /// Pixels as indices into a palette of at most 256 colours, `None` if there are more colours.
+    fn indexed(&self) -> Option<(Vec<u8>, Vec<Rgb>)> {
+        let mut palette = vec![];
+        let mut lookup = HashMap::new();
+
+        let indices = self
+            .pixels
+            .iter()
+            .map(|&pixel| {
+                if let Some(&index) = lookup.get(&pixel) {
+                    return Some(index);
+                }
+
+                let index = u8::try_from(palette.len()).ok()?;
+                palette.push(pixel);
+                lookup.insert(pixel, index);
+                Some(index)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((indices, palette))
+    }
+}
+
+/// Animated GIF written to `data/viz/<day>/`, one [`Image`] per frame.
+///
+/// Frames are encoded as they are pushed, so long simulations don't have to be kept in memory.
+pub struct Animation {
+    encoder: gif::Encoder<BufWriter<File>>,
+    path: PathBuf,
+    width: usize,
+    height: usize,
+    delay: u16,
+    frames: usize,
+}
+
+impl Animation {
+    /// Starts a looping animation, `delay` is the time between frames in hundredths of a second.
+    pub fn create(day: Day, file_name: &str, width: usize, height: usize, delay: u16) -> io::Result<Self> {
+        let path = viz::path(day, file_name)?;
+        let file = BufWriter::new(File::create(&path)?);
+
+        let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &[]).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+        Ok(Self {
+            encoder,
+            path,
+            width,
+            height,
+            delay,
+            frames: 0,
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Appends a frame, which must have the size the animation was created with and at most 256 colours.
+    pub fn push(&mut self, image: &Image) -> io::Result<()> {
+        if image.width != self.width || image.height != self.height {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame size doesn't match the animation",
+            ));
+        }
+
+        let (indices, palette) = image
+            .indexed()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "frame has more than 256 colours"))?;
+
+        let mut frame =
+            gif::Frame::from_palette_pixels(self.width as u16, self.height as u16, indices, palette.concat(), None);
+        frame.delay = self.delay;
+
+        self.encoder.write_frame(&frame).map_err(io::Error::other)?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{colour, Image};
+    use crate::utils::location::Location;
+
+    #[test]
+    fn draws_scaled_grid() {
+        let grid = vec![vec![true, false]];
+        let image = Image::from_grid(&grid, 2, |&on| if on { colour::WHITE } else { colour::RED });
+
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.get(Location::new(1, 1)), Some(colour::WHITE));
+        assert_eq!(image.get(Location::new(2, 0)), Some(colour::RED));
+        assert_eq!(image.get(Location::new(4, 0)), None);
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 2 * 3);
+        assert!(image.to_png().starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template;
+use crate::Day;
+
+pub mod dot;
+pub mod image;
+pub mod terminal;
+
+/// Whether the solution was started with `--viz`, e.g. by `cargo solve 20 --viz`.
+pub fn is_enabled() -> bool {
+    env::args().any(|x| x == "--viz")
+}
+
+/// Path of a visualization file of a day, `data/viz/<day>/<file_name>`. Creates the directory if needed.
+pub fn path(day: Day, file_name: &str) -> io::Result<PathBuf> {
+    let directory = template::data_dir().join("viz").join(day.to_string());
+    fs::create_dir_all(&directory)?;
+
+    Ok(directory.join(file_name))
+}
+
+/// Writes a visualization of a day to `data/viz/<day>/<file_name>` and returns its path.
+pub fn write(day: Day, file_name: &str, contents: impl AsRef<[u8]>) -> io::Result<PathBuf> {
+    let path = path(day, file_name)?;
+    fs::write(&path, contents)?;
+    Ok(path)
+}
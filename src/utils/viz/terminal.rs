@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::utils::grid::Regions;
+use crate::utils::location::{Access2d, Location};
+use crate::utils::rect::Rect;
+
+/// The 8 basic terminal colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    /// Colours that stand out on both dark and light terminals, used for regions.
+    const PALETTE: [Colour; 6] = [
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+    ];
+
+    fn foreground(self) -> String {
+        format!("\x1b[{}m", 30 + self as u8)
+    }
+
+    fn background(self) -> String {
+        format!("\x1b[{}m", 40 + self as u8)
+    }
+}
+
+/// How a single cell is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    pub symbol: char,
+    pub colour: Option<Colour>,
+    pub background: Option<Colour>,
+    pub bold: bool,
+}
+
+impl Glyph {
+    pub fn new(symbol: char, colour: Colour) -> Self {
+        Self {
+            colour: Some(colour),
+            ..Self::from(symbol)
+        }
+    }
+
+    /// First character of the value's [`Display`] output, e.g. for tiles that display as their input symbol.
+    pub fn of(value: &impl Display) -> Self {
+        Self::from(value.to_string().chars().next().unwrap_or(' '))
+    }
+}
+
+impl From<char> for Glyph {
+    fn from(symbol: char) -> Self {
+        Self {
+            symbol,
+            colour: None,
+            background: None,
+            bold: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Overlay {
+    /// Keeps the symbol, changes the background.
+    Highlight(HashSet<Location<i32>>, Colour),
+    /// Replaces the symbol.
+    Mark(HashSet<Location<i32>>, char, Colour),
+}
+
+/// Draws a grid in the terminal, with overlays on top of the cells. Later overlays win.
+pub struct GridRenderer<'a, T> {
+    grid: &'a Vec<Vec<T>>,
+    glyph: Box<dyn Fn(&T) -> Glyph + 'a>,
+    overlays: Vec<Overlay>,
+    viewport: Option<Rect<i32>>,
+    colours: bool,
+}
+
+impl<'a, T> GridRenderer<'a, T> {
+    pub fn new(grid: &'a Vec<Vec<T>>, glyph: impl Fn(&T) -> Glyph + 'a) -> Self {
+        Self {
+            grid,
+            glyph: Box::new(glyph),
+            overlays: vec![],
+            viewport: None,
+            colours: true,
+        }
+    }
+
+    /// Colours the background of the given cells, e.g. a path.
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = Location<i32>>, colour: Colour) -> Self {
+        self.overlays.push(Overlay::Highlight(cells.into_iter().collect(), colour));
+        self
+    }
+
+    /// Colours the background of every `true` cell, e.g. the output of [`crate::utils::grid::flood_fill`].
+    pub fn highlight_mask(self, mask: &Vec<Vec<bool>>, colour: Colour) -> Self {
+        let cells = mask
+            .iter_2d_keys()
+            .map(|loc| loc.map(|a| a as i32))
+            .filter(|&loc| mask.get_2d(loc) == Some(&true))
+            .collect::<Vec<_>>();
+        self.highlight(cells, colour)
+    }
+
+    /// Draws `symbol` over the given cells.
+    pub fn mark(mut self, cells: impl IntoIterator<Item = Location<i32>>, symbol: char, colour: Colour) -> Self {
+        self.overlays.push(Overlay::Mark(cells.into_iter().collect(), symbol, colour));
+        self
+    }
+
+    /// Colours the background of every region, cycling through a small palette.
+    pub fn regions(mut self, regions: &Regions) -> Self {
+        let mut cells = vec![HashSet::new(); Colour::PALETTE.len()];
+        for loc in regions.labels.iter_2d_keys().map(|loc| loc.map(|a| a as i32)) {
+            if let Some(label) = regions.label(loc) {
+                cells[label % Colour::PALETTE.len()].insert(loc);
+            }
+        }
+
+        for (cells, colour) in cells.into_iter().zip(Colour::PALETTE) {
+            self.overlays.push(Overlay::Highlight(cells, colour));
+        }
+        self
+    }
+
+    /// Only draws the cells inside `viewport`.
+    pub fn viewport(mut self, viewport: Rect<i32>) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Draws symbols only, without any escape codes.
+    pub fn without_colours(mut self) -> Self {
+        self.colours = false;
+        self
+    }
+
+    fn glyph_at(&self, loc: Location<i32>) -> Option<Glyph> {
+        let mut glyph = (self.glyph)(self.grid.get_2d(loc)?);
+
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Highlight(cells, colour) if cells.contains(&loc) => glyph.background = Some(*colour),
+                Overlay::Mark(cells, symbol, colour) if cells.contains(&loc) => {
+                    glyph = Glyph {
+                        bold: true,
+                        ..Glyph::new(*symbol, *colour)
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(glyph)
+    }
+
+    fn bounds(&self) -> Rect<i32> {
+        let width = self.grid.iter().map(Vec::len).max().unwrap_or(0);
+        let bounds = Rect::new(Location::new(0, 0), Location::new(width as i32, self.grid.len() as i32));
+
+        match self.viewport {
+            Some(viewport) => bounds
+                .intersection(&viewport)
+                .unwrap_or(Rect::new(bounds.start, bounds.start)),
+            None => bounds,
+        }
+    }
+}
+
+impl<T> Display for GridRenderer<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bounds = self.bounds();
+
+        for y in bounds.start.y..bounds.end.y {
+            for x in bounds.start.x..bounds.end.x {
+                let Some(glyph) = self.glyph_at(Location::new(x, y)) else {
+                    write!(f, " ")?;
+                    continue;
+                };
+
+                if !self.colours {
+                    write!(f, "{}", glyph.symbol)?;
+                    continue;
+                }
+
+                let bold = if glyph.bold { ANSI_BOLD } else { "" };
+                let colour = glyph.colour.map(Colour::foreground).unwrap_or_default();
+                let background = glyph.background.map(Colour::background).unwrap_or_default();
+                let is_styled = glyph.bold || glyph.colour.is_some() || glyph.background.is_some();
+
+                if is_styled {
+                    write!(f, "{bold}{colour}{background}{}{ANSI_RESET}", glyph.symbol)?;
+                } else {
+                    write!(f, "{}", glyph.symbol)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Colour, GridRenderer};
+    use crate::utils::grid::{label_regions, Neighbourhood};
+    use crate::utils::location::Location;
+    use crate::utils::rect::Rect;
+
+    #[test]
+    fn marks_without_colours() {
+        let grid = vec![vec!['.', '#'], vec!['#', '.']];
+        let renderer = GridRenderer::new(&grid, |&c: &char| c.into())
+            .mark([Location::new(0, 0)], '@', Colour::Red)
+            .without_colours();
+
+        assert_eq!(renderer.to_string(), "@#\n#.\n");
+    }
+
+    #[test]
+    fn overlays_and_viewport() {
+        let grid = vec!["#..#".chars().collect(), "#.##".chars().collect(), "....".chars().collect()];
+
+        let renderer = GridRenderer::new(&grid, |&c: &char| c.into())
+            .mark([Location::new(1, 1), Location::new(2, 2)], 'O', Colour::Red)
+            .viewport(Rect::new(Location::new(1, 1), Location::new(10, 3)))
+            .without_colours();
+        assert_eq!(renderer.to_string(), "O##\n.O.\n");
+
+        let regions = label_regions(&grid, Neighbourhood::Orthogonal, |&c| c == '#');
+        let coloured = GridRenderer::new(&grid, |&c: &char| c.into()).regions(&regions).to_string();
+        assert_eq!(coloured.lines().next(), Some("\x1b[41m#\x1b[0m..\x1b[42m#\x1b[0m"));
+    }
+}